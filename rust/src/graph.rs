@@ -1,24 +1,62 @@
+pub mod approximation;
+pub mod arborescence;
+pub mod bottleneck;
+pub mod centrality;
+pub mod cliques;
+pub mod community;
+pub mod constrained_paths;
+pub mod constraints;
+pub mod critical_path;
+pub mod cycles;
+pub mod dominators;
+pub mod isomorphism;
+pub mod lca;
+pub mod metrics;
+pub mod min_cut;
+pub mod operations;
+pub mod planarity;
+pub mod reachability;
+pub mod small_weights;
+pub mod two_sat;
+pub mod voronoi;
+
 use crate::graph::Distance::Dist;
 use std::cell::RefCell;
 use std::cmp::{min, Ordering};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::fmt::{Display, Formatter, Write};
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Deref};
 use std::rc::Rc;
-use std::slice::Iter;
+use std::slice::{Iter, IterMut};
 
 type Predecessor<T: Eq + Hash + Copy> = Option<T>;
 
+pub trait Weight: Copy + Ord + Add<Output = Self> {
+    fn zero() -> Self;
+}
+
+macro_rules! impl_weight {
+    ($($t:ty),*) => {
+        $(impl Weight for $t {
+            fn zero() -> Self {
+                0
+            }
+        })*
+    };
+}
+
+impl_weight!(u8, u16, u32, u64, usize, i32, i64);
+
 #[derive(Clone, Copy, Eq, PartialEq)]
-struct VertAttributes<T: Eq + Hash + Copy> {
+struct VertAttributes<T: Eq + Hash + Copy, W: Copy + Ord = u32> {
     id: T,
     predecessor: Predecessor<T>,
-    distance: Distance,
+    distance: Distance<W>,
 }
 
-impl<T: Eq + Hash + Copy> VertAttributes<T> {
-    fn new_inf(vert: T) -> VertAttributes<T> {
+impl<T: Eq + Hash + Copy, W: Weight> VertAttributes<T, W> {
+    fn new_inf(vert: T) -> VertAttributes<T, W> {
         VertAttributes {
             id: vert,
             predecessor: Predecessor::None,
@@ -26,15 +64,15 @@ impl<T: Eq + Hash + Copy> VertAttributes<T> {
         }
     }
 
-    fn new_zero(vert: T) -> VertAttributes<T> {
+    fn new_zero(vert: T) -> VertAttributes<T, W> {
         VertAttributes {
             id: vert,
             predecessor: Predecessor::None,
-            distance: Distance::Dist(0),
+            distance: Distance::Dist(W::zero()),
         }
     }
 
-    fn new_dist(vert: T, distance: Distance) -> VertAttributes<T> {
+    fn new_dist(vert: T, distance: Distance<W>) -> VertAttributes<T, W> {
         VertAttributes {
             id: vert,
             predecessor: Predecessor::None,
@@ -43,22 +81,25 @@ impl<T: Eq + Hash + Copy> VertAttributes<T> {
     }
 }
 
-impl<T: Eq + Hash + Copy> PartialOrd<Self> for VertAttributes<T> {
+impl<T: Eq + Hash + Copy, W: Weight> PartialOrd<Self> for VertAttributes<T, W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Option::Some(self.cmp(other))
     }
 }
 
-impl<T: Eq + Hash + Copy> Ord for VertAttributes<T> {
+impl<T: Eq + Hash + Copy, W: Weight> Ord for VertAttributes<T, W> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.distance.cmp(&other.distance)
     }
 }
 
-pub struct PositionTrackingMinHeap<T: Eq + Hash + Copy>(Vec<VertAttributes<T>>, HashMap<T, usize>);
+pub struct PositionTrackingMinHeap<T: Eq + Hash + Copy, W: Copy + Ord = u32>(
+    Vec<VertAttributes<T, W>>,
+    HashMap<T, usize>,
+);
 
-impl<T: Eq + Hash + Copy> PositionTrackingMinHeap<T> {
-    pub fn new() -> PositionTrackingMinHeap<T> {
+impl<T: Eq + Hash + Copy, W: Weight> PositionTrackingMinHeap<T, W> {
+    pub fn new() -> PositionTrackingMinHeap<T, W> {
         PositionTrackingMinHeap(Vec::new(), HashMap::new())
     }
 
@@ -78,7 +119,7 @@ impl<T: Eq + Hash + Copy> PositionTrackingMinHeap<T> {
     }
 
     fn _swap(&mut self, first: usize, second: usize) {
-        let tmp: VertAttributes<T> = (*self.0.get(first).unwrap()).clone();
+        let tmp: VertAttributes<T, W> = (*self.0.get(first).unwrap()).clone();
         self.1.insert(tmp.id, second);
         let snd = self.0.get(second).unwrap();
         self.1.insert(snd.id, first);
@@ -86,13 +127,13 @@ impl<T: Eq + Hash + Copy> PositionTrackingMinHeap<T> {
         *self.0.get_mut(second).unwrap() = tmp;
     }
 
-    pub fn get(&self, index: usize) -> Option<&VertAttributes<T>> {
+    pub fn get(&self, index: usize) -> Option<&VertAttributes<T, W>> {
         self.0.get(index)
     }
 
     pub fn min_heapify(&mut self, index: usize) {
-        let left = PositionTrackingMinHeap::<T>::_left(index);
-        let right = PositionTrackingMinHeap::<T>::_right(index);
+        let left = PositionTrackingMinHeap::<T, W>::_left(index);
+        let right = PositionTrackingMinHeap::<T, W>::_right(index);
         let mut smallest = left;
         let vec_len = self.0.len();
         if left < vec_len && self.0.get(left).unwrap() < self.0.get(index).unwrap() {
@@ -109,7 +150,7 @@ impl<T: Eq + Hash + Copy> PositionTrackingMinHeap<T> {
         }
     }
 
-    pub fn extract_min(&mut self) -> VertAttributes<T> {
+    pub fn extract_min(&mut self) -> VertAttributes<T, W> {
         let len = self.0.len();
         let last_id = self.0.get(len - 1).unwrap().id;
         let min = self.0.swap_remove(0);
@@ -119,13 +160,13 @@ impl<T: Eq + Hash + Copy> PositionTrackingMinHeap<T> {
         min
     }
 
-    pub fn insert(&mut self, value: VertAttributes<T>) {
+    pub fn insert(&mut self, value: VertAttributes<T, W>) {
         self.0.push(value.clone());
         self.1.insert(value.id, self.0.len() - 1);
         self.heap_decrease_key(self.0.len() - 1, value);
     }
 
-    pub fn heap_decrease_key(&mut self, index: usize, value: VertAttributes<T>) {
+    pub fn heap_decrease_key(&mut self, index: usize, value: VertAttributes<T, W>) {
         let current = self.0.get(index).unwrap();
         if value > *current {
             panic!("New key is greater than current")
@@ -135,11 +176,11 @@ impl<T: Eq + Hash + Copy> PositionTrackingMinHeap<T> {
         while idx > 0
             && self
                 .0
-                .get(PositionTrackingMinHeap::<T>::_parent(idx))
+                .get(PositionTrackingMinHeap::<T, W>::_parent(idx))
                 .unwrap()
                 > self.0.get(idx).unwrap()
         {
-            let parent = PositionTrackingMinHeap::<T>::_parent(idx);
+            let parent = PositionTrackingMinHeap::<T, W>::_parent(idx);
             self._swap(idx, parent);
             idx = parent;
         }
@@ -150,43 +191,82 @@ impl<T: Eq + Hash + Copy> PositionTrackingMinHeap<T> {
     }
 }
 
-#[derive(Clone, Hash, Eq, PartialEq)]
-pub struct Edge {
-    value: Option<u32>,
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct EdgeId(usize);
+
+impl EdgeId {
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+impl Display for EdgeId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(format!("#{}", self.0).as_str())
+    }
+}
+
+#[derive(Clone)]
+pub struct Edge<E = Option<u32>> {
+    id: Option<EdgeId>,
+    value: E,
     from: u32,
     to: u32,
 }
 
-impl Edge {
-    pub fn new_weighted(value: u32, from: u32, to: u32) -> Edge {
-        Edge {
-            value: Option::Some(value),
-            from,
-            to,
-        }
+impl<E: PartialEq> PartialEq for Edge<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.from == other.from && self.to == other.to && self.value == other.value
     }
+}
 
-    pub fn new(from: u32, to: u32) -> Edge {
+impl<E: Eq> Eq for Edge<E> {}
+
+impl<E: Hash> Hash for Edge<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+        self.from.hash(state);
+        self.to.hash(state);
+    }
+}
+
+impl<E> Edge<E> {
+    pub fn with_value(value: E, from: u32, to: u32) -> Edge<E> {
         Edge {
-            value: Option::None,
+            id: Option::None,
+            value,
             from,
             to,
         }
     }
 
-    pub fn reverse(&self) -> Edge {
-        Edge {
-            value: self.value,
-            from: self.to,
-            to: self.from,
-        }
+    pub fn id(&self) -> Option<EdgeId> {
+        self.id
     }
 
-    pub fn compare_values(&self, other: &Edge) -> Ordering {
-        self.value.cmp(&other.value)
+    pub fn is_self_loop(&self) -> bool {
+        self.from == self.to
     }
 
-    pub fn same_ignore_direction(&self, other: &Edge) -> bool {
+    pub fn from(&self) -> u32 {
+        self.from
+    }
+
+    pub fn to(&self) -> u32 {
+        self.to
+    }
+
+    pub fn value(&self) -> &E {
+        &self.value
+    }
+
+    pub fn value_mut(&mut self) -> &mut E {
+        &mut self.value
+    }
+}
+
+impl<E: PartialEq> Edge<E> {
+    pub fn same_ignore_direction(&self, other: &Edge<E>) -> bool {
         let mut result = self.eq(other);
         if !result {
             if self.to == other.from && self.from == other.to {
@@ -199,6 +279,41 @@ impl Edge {
     }
 }
 
+impl<E: Clone> Edge<E> {
+    pub fn reverse(&self) -> Edge<E> {
+        Edge {
+            id: self.id,
+            value: self.value.clone(),
+            from: self.to,
+            to: self.from,
+        }
+    }
+}
+
+impl Edge {
+    pub fn new_weighted(value: u32, from: u32, to: u32) -> Edge {
+        Edge {
+            id: Option::None,
+            value: Option::Some(value),
+            from,
+            to,
+        }
+    }
+
+    pub fn new(from: u32, to: u32) -> Edge {
+        Edge {
+            id: Option::None,
+            value: Option::None,
+            from,
+            to,
+        }
+    }
+
+    pub fn compare_values(&self, other: &Edge) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
 impl Display for Edge {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if let Some(v) = self.value {
@@ -211,17 +326,17 @@ impl Display for Edge {
     }
 }
 
-pub enum Edges {
+pub enum Edges<E = Option<u32>> {
     Empty,
-    Edges(Vec<Edge>),
+    Edges(Vec<Edge<E>>),
 }
 
-impl Edges {
-    pub fn new() -> Edges {
+impl<E> Edges<E> {
+    pub fn new() -> Edges<E> {
         Edges::Empty
     }
 
-    pub fn from_edge(edge: Edge) -> Edges {
+    pub fn from_edge(edge: Edge<E>) -> Edges<E> {
         let mut v = Vec::new();
         v.push(edge);
         Edges::Edges(v)
@@ -236,10 +351,10 @@ impl Edges {
         }
     }
 
-    pub fn add_edge(&mut self, edge: Edge) {
+    pub fn add_edge(&mut self, edge: Edge<E>) {
         match self {
             Edges::Empty => {
-                let mut edges: Vec<Edge> = Vec::new();
+                let mut edges: Vec<Edge<E>> = Vec::new();
                 edges.push(edge);
                 *self = Edges::Edges(edges);
             }
@@ -256,12 +371,19 @@ impl Edges {
         }
     }
 
-    pub fn iter(&self) -> Iter<'_, Edge> {
+    pub fn iter(&self) -> Iter<'_, Edge<E>> {
         match self {
             Edges::Empty => [].iter(),
             Edges::Edges(edges) => edges.iter(),
         }
     }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, Edge<E>> {
+        match self {
+            Edges::Empty => [].iter_mut(),
+            Edges::Edges(edges) => edges.iter_mut(),
+        }
+    }
 }
 
 impl Display for Edges {
@@ -285,43 +407,265 @@ impl Display for Edges {
     }
 }
 
-pub struct Graph {
-    adj: HashMap<u32, Edges>,
+pub enum ParallelEdgePolicy<E = Option<u32>> {
+    AllowMultiEdges,
+    RejectDuplicates,
+    KeepMinimum(fn(&E, &E) -> Ordering),
+    SumWeights(fn(&E, &E) -> E),
 }
 
-impl Graph {
-    pub fn new() -> Graph {
+impl<E> Clone for ParallelEdgePolicy<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for ParallelEdgePolicy<E> {}
+
+impl ParallelEdgePolicy {
+    pub fn keep_minimum() -> ParallelEdgePolicy {
+        ParallelEdgePolicy::KeepMinimum(|a, b| a.unwrap_or(1).cmp(&b.unwrap_or(1)))
+    }
+
+    pub fn sum_weights() -> ParallelEdgePolicy {
+        ParallelEdgePolicy::SumWeights(|a, b| {
+            Option::Some(a.unwrap_or(1).saturating_add(b.unwrap_or(1)))
+        })
+    }
+}
+
+pub struct Graph<N = (), E = Option<u32>> {
+    adj: BTreeMap<u32, Edges<E>>,
+    payloads: BTreeMap<u32, N>,
+    policy: ParallelEdgePolicy<E>,
+    edge_endpoints: HashMap<EdgeId, (u32, u32)>,
+    next_edge_id: usize,
+}
+
+impl<N, E> Graph<N, E> {
+    pub fn new() -> Graph<N, E> {
+        Graph::with_policy(ParallelEdgePolicy::AllowMultiEdges)
+    }
+
+    pub fn with_policy(policy: ParallelEdgePolicy<E>) -> Graph<N, E> {
         Graph {
-            adj: HashMap::new(),
+            adj: BTreeMap::new(),
+            payloads: BTreeMap::new(),
+            policy,
+            edge_endpoints: HashMap::new(),
+            next_edge_id: 0,
         }
     }
 
-    pub fn add_adj(&mut self, edge: Edge) {
-        if let Some(v) = self.adj.get_mut(&edge.from) {
-            v.add_edge(edge.clone());
-        } else {
-            let mut l = Vec::new();
-            let key = edge.from;
-            l.push(edge.clone());
-            self.adj.insert(key, Edges::Edges(l));
+    pub fn policy(&self) -> ParallelEdgePolicy<E> {
+        self.policy
+    }
+
+    pub fn insert_vertex(&mut self, vert: u32, payload: N) -> Option<N> {
+        self.adj.entry(vert).or_insert(Edges::Empty);
+        self.payloads.insert(vert, payload)
+    }
+
+    pub fn contains_vertex(&self, vert: u32) -> bool {
+        self.adj.contains_key(&vert)
+    }
+
+    pub fn vertex(&self, vert: u32) -> Option<&N> {
+        self.payloads.get(&vert)
+    }
+
+    pub fn vertex_mut(&mut self, vert: u32) -> Option<&mut N> {
+        self.payloads.get_mut(&vert)
+    }
+
+    pub fn vertices(&self) -> Vec<u32> {
+        self.adj.keys().copied().collect()
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.adj.len()
+    }
+
+    pub(crate) fn neighbour_sets(&self) -> BTreeMap<u32, BTreeSet<u32>> {
+        let mut result: BTreeMap<u32, BTreeSet<u32>> =
+            self.adj.keys().map(|v| (*v, BTreeSet::new())).collect();
+        for (vert, edges) in self.adj.iter() {
+            for edge in edges.iter().filter(|e| e.to != *vert) {
+                result.get_mut(vert).unwrap().insert(edge.to);
+                result.get_mut(&edge.to).unwrap().insert(*vert);
+            }
         }
-        if self.adj.get(&edge.to).is_none() {
-            let key = edge.to;
-            self.adj.insert(key, Edges::Empty);
+        result
+    }
+
+    pub fn edge_count(&self) -> usize {
+        let mut seen: HashSet<EdgeId> = HashSet::new();
+        self.adj
+            .values()
+            .flat_map(|edges| edges.iter())
+            .filter(|edge| edge.id.is_none_or(|id| seen.insert(id)))
+            .count()
+    }
+
+    pub fn edges_from(&self, vert: u32) -> Iter<'_, Edge<E>> {
+        match self.adj.get(&vert) {
+            None => [].iter(),
+            Some(edges) => edges.iter(),
         }
     }
 
-    pub fn add_adj_both(&mut self, edge: Edge) {
-        if let Some(v) = self.adj.get_mut(&edge.from) {
-            v.add_edge(edge.clone());
-        } else {
-            self.adj.insert(edge.from, Edges::from_edge(edge.clone()));
+    pub fn edge(&self, from: u32, to: u32) -> Option<&E> {
+        self.edges_from(from)
+            .find(|edge| edge.to == to)
+            .map(|edge| &edge.value)
+    }
+
+    fn is_undirected(&self, id: EdgeId) -> bool {
+        let (from, to) = match self.edge_endpoints.get(&id) {
+            None => return false,
+            Some(endpoints) => *endpoints,
+        };
+        let mut halves = self
+            .edges_from(from)
+            .filter(|e| e.id == Option::Some(id))
+            .count();
+        if from != to {
+            halves += self
+                .edges_from(to)
+                .filter(|e| e.id == Option::Some(id))
+                .count();
         }
-        if let Some(v) = self.adj.get_mut(&edge.to) {
-            v.add_edge(edge.reverse());
-        } else {
-            self.adj.insert(edge.to, Edges::from_edge(edge.reverse()));
+        halves == 2
+    }
+
+    pub(crate) fn is_undirected_graph(&self) -> bool {
+        let mut halves: HashMap<EdgeId, usize> = HashMap::new();
+        for edge in self.adj.values().flat_map(|edges| edges.iter()) {
+            match edge.id {
+                None => return false,
+                Some(id) => *halves.entry(id).or_default() += 1,
+            }
         }
+        halves.values().all(|count| *count == 2)
+    }
+
+    pub fn edges_between(&self, from: u32, to: u32) -> Vec<&Edge<E>> {
+        self.edges_from(from).filter(|edge| edge.to == to).collect()
+    }
+
+    pub fn edge_by_id(&self, id: EdgeId) -> Option<&Edge<E>> {
+        let (from, _) = self.edge_endpoints.get(&id)?;
+        self.edges_from(*from)
+            .find(|edge| edge.id == Option::Some(id))
+    }
+
+    pub fn set_edge_value(&mut self, id: EdgeId, value: E)
+    where
+        E: Clone,
+    {
+        if let Some((from, to)) = self.edge_endpoints.get(&id).copied() {
+            for vert in [from, to].iter() {
+                if let Some(edges) = self.adj.get_mut(vert) {
+                    for edge in edges.iter_mut() {
+                        if edge.id == Option::Some(id) {
+                            edge.value = value.clone();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn remove_edge(&mut self, id: EdgeId) -> Option<Edge<E>> {
+        let (from, to) = self.edge_endpoints.remove(&id)?;
+        let mut result = Option::None;
+        for vert in [from, to].iter() {
+            if let Some(Edges::Edges(edges)) = self.adj.get_mut(vert) {
+                while let Some(pos) = edges.iter().position(|edge| edge.id == Option::Some(id)) {
+                    let removed = edges.remove(pos);
+                    if removed.from == from && result.is_none() {
+                        result = Option::Some(removed);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn next_id(&mut self) -> EdgeId {
+        let id = EdgeId(self.next_edge_id);
+        self.next_edge_id += 1;
+        id
+    }
+}
+
+impl<N: Default, E: Clone> Graph<N, E> {
+    pub fn add_adj(&mut self, edge: Edge<E>) -> Option<EdgeId> {
+        self.insert_edge(edge, false)
+    }
+
+    pub fn add_edge(&mut self, from: u32, to: u32, value: E) -> Option<EdgeId> {
+        self.add_adj(Edge::with_value(value, from, to))
+    }
+
+    pub fn from_edges_both(edges: &Edges<E>) -> Graph<N, E> {
+        let mut result = Graph::new();
+        for edge in edges.iter() {
+            result.add_adj_both(edge.clone());
+        }
+        result
+    }
+
+    pub fn add_vertex(&mut self, vert: u32) {
+        self.adj.entry(vert).or_insert(Edges::Empty);
+        self.payloads.entry(vert).or_default();
+    }
+
+    pub fn add_adj_both(&mut self, edge: Edge<E>) -> Option<EdgeId> {
+        self.insert_edge(edge, true)
+    }
+
+    fn insert_edge(&mut self, edge: Edge<E>, both: bool) -> Option<EdgeId> {
+        self.add_vertex(edge.from);
+        self.add_vertex(edge.to);
+        self.link_edge(edge, both, Option::None)
+    }
+}
+
+impl<N, E: Clone> Graph<N, E> {
+    fn link_edge(&mut self, mut edge: Edge<E>, both: bool, id: Option<EdgeId>) -> Option<EdgeId> {
+        let existing = self
+            .edges_from(edge.from)
+            .find(|e| e.to == edge.to)
+            .map(|e| (e.id.unwrap(), e.value.clone()));
+        match (self.policy, existing) {
+            (ParallelEdgePolicy::AllowMultiEdges, _) | (_, None) => {}
+            (ParallelEdgePolicy::RejectDuplicates, Some(_)) => return Option::None,
+            (ParallelEdgePolicy::KeepMinimum(compare), Some((id, value))) => {
+                if compare(&edge.value, &value) == Ordering::Less {
+                    self.set_edge_value(id, edge.value);
+                }
+                return Option::Some(id);
+            }
+            (ParallelEdgePolicy::SumWeights(sum), Some((id, value))) => {
+                self.set_edge_value(id, sum(&value, &edge.value));
+                return Option::Some(id);
+            }
+        }
+        let id = match id {
+            Some(id) if !self.edge_endpoints.contains_key(&id) => {
+                self.next_edge_id = self.next_edge_id.max(id.0 + 1);
+                id
+            }
+            _ => self.next_id(),
+        };
+        edge.id = Option::Some(id);
+        self.edge_endpoints.insert(id, (edge.from, edge.to));
+        if both {
+            self.adj.get_mut(&edge.to).unwrap().add_edge(edge.reverse());
+        }
+        self.adj.get_mut(&edge.from).unwrap().add_edge(edge);
+        Option::Some(id)
     }
 }
 
@@ -363,7 +707,7 @@ impl Display for Vertices {
     }
 }
 
-fn visited_map(graph: &Graph) -> HashMap<u32, bool> {
+fn visited_map<N, E>(graph: &Graph<N, E>) -> HashMap<u32, bool> {
     let mut visited = HashMap::new();
     for (key, _) in graph.adj.iter() {
         visited.insert(*key, false);
@@ -371,7 +715,7 @@ fn visited_map(graph: &Graph) -> HashMap<u32, bool> {
     visited
 }
 
-pub fn breadth_first_search(graph: &Graph, first: u32) -> Vertices {
+pub fn breadth_first_search<N, E>(graph: &Graph<N, E>, first: u32) -> Vertices {
     let mut visited = visited_map(graph);
     visited.insert(first, true);
     let mut result: Vertices = Vertices::new();
@@ -399,7 +743,12 @@ pub fn breadth_first_search(graph: &Graph, first: u32) -> Vertices {
     result
 }
 
-fn dfs_visit(vert: &u32, graph: &Graph, visited: &mut HashMap<u32, bool>, result: &mut Vertices) {
+fn dfs_visit<N, E>(
+    vert: &u32,
+    graph: &Graph<N, E>,
+    visited: &mut HashMap<u32, bool>,
+    result: &mut Vertices,
+) {
     visited.insert(*vert, true);
     result.push(*vert);
     if let Some(e) = graph.adj.get(vert) {
@@ -418,7 +767,7 @@ fn dfs_visit(vert: &u32, graph: &Graph, visited: &mut HashMap<u32, bool>, result
     }
 }
 
-pub fn depth_first_search(graph: &Graph) -> Vertices {
+pub fn depth_first_search<N, E>(graph: &Graph<N, E>) -> Vertices {
     let mut result: Vertices = Vertices::new();
     let mut visited = visited_map(graph);
     for (key, _) in graph.adj.iter() {
@@ -431,7 +780,7 @@ pub fn depth_first_search(graph: &Graph) -> Vertices {
     result
 }
 
-pub fn depth_first_search_iter(graph: &Graph) -> Vertices {
+pub fn depth_first_search_iter<N, E>(graph: &Graph<N, E>) -> Vertices {
     let mut visited = visited_map(graph);
     let mut result = Vertices::new();
     let mut stack: Vec<u32> = Vec::new();
@@ -467,23 +816,75 @@ pub fn depth_first_search_iter(graph: &Graph) -> Vertices {
     result
 }
 
-fn edges_sorted_asc(graph: &Graph) -> Vec<Edge> {
-    let mut set: HashSet<Edge> = HashSet::new();
-    for (_, edges) in graph.adj.iter() {
-        match edges {
-            Edges::Empty => {}
-            Edges::Edges(e) => {
-                for edge in e.iter() {
-                    let rev = edge.reverse();
-                    if !(set.contains(edge) || set.contains(&rev)) {
-                        set.insert(edge.clone());
+pub fn strongly_connected_components<N, E>(graph: &Graph<N, E>) -> Vec<Vec<u32>> {
+    let mut result = Vec::new();
+    let mut index: HashMap<u32, usize> = HashMap::new();
+    let mut low: HashMap<u32, usize> = HashMap::new();
+    let mut on_stack: HashSet<u32> = HashSet::new();
+    let mut stack: Vec<u32> = Vec::new();
+    let mut calls: Vec<(u32, usize)> = Vec::new();
+    for (root, _) in graph.adj.iter() {
+        if index.contains_key(root) {
+            continue;
+        }
+        index.insert(*root, index.len());
+        low.insert(*root, index.len() - 1);
+        stack.push(*root);
+        on_stack.insert(*root);
+        calls.push((*root, 0));
+        while let Some((vert, position)) = calls.last_mut() {
+            let vert = *vert;
+            let edges = graph.adj.get(&vert).unwrap().iter().as_slice();
+            if let Some(edge) = edges.get(*position) {
+                *position += 1;
+                if !index.contains_key(&edge.to) {
+                    index.insert(edge.to, index.len());
+                    low.insert(edge.to, index.len() - 1);
+                    stack.push(edge.to);
+                    on_stack.insert(edge.to);
+                    calls.push((edge.to, 0));
+                } else if on_stack.contains(&edge.to) {
+                    let lowest = min(low[&vert], index[&edge.to]);
+                    low.insert(vert, lowest);
+                }
+            } else {
+                calls.pop();
+                if let Some((parent, _)) = calls.last() {
+                    let lowest = min(low[parent], low[&vert]);
+                    low.insert(*parent, lowest);
+                }
+                if low[&vert] == index[&vert] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack.remove(&member);
+                        component.push(member);
+                        if member == vert {
+                            break;
+                        }
                     }
+                    result.push(component);
                 }
             }
         }
     }
-    let mut result: Vec<Edge> = set.into_iter().collect();
-    result.sort_by(|a, b| a.compare_values(b));
+    result.reverse();
+    result
+}
+
+fn edges_sorted_asc_by<N, E: Clone, W: Ord>(
+    graph: &Graph<N, E>,
+    weight: &impl Fn(&E) -> W,
+) -> Vec<Edge<E>> {
+    let mut result: Vec<Edge<E>> = Vec::new();
+    let mut seen: HashSet<EdgeId> = HashSet::new();
+    for (_, edges) in graph.adj.iter() {
+        for edge in edges.iter() {
+            if edge.id.map(|id| seen.insert(id)).unwrap_or(true) {
+                result.push(edge.clone());
+            }
+        }
+    }
+    result.sort_by_key(|edge| weight(&edge.value));
     result
 }
 
@@ -499,12 +900,19 @@ fn union_sets(sets: &mut HashMap<u32, u32>, first: u32, second: u32) {
 }
 
 pub fn minimal_spanning_tree_kruskal(graph: &Graph) -> Edges {
+    minimal_spanning_tree_kruskal_by(graph, |value| *value)
+}
+
+pub fn minimal_spanning_tree_kruskal_by<N, E: Clone, W: Ord>(
+    graph: &Graph<N, E>,
+    weight: impl Fn(&E) -> W,
+) -> Edges<E> {
     let mut result = Edges::new();
     let mut sets: HashMap<u32, u32> = HashMap::new();
     for (vert, _) in graph.adj.iter() {
         sets.insert(*vert, *vert);
     }
-    let edges = edges_sorted_asc(graph);
+    let edges = edges_sorted_asc_by(graph, &weight);
     for edge in edges.iter() {
         if !(sets.get(&edge.from).unwrap() == sets.get(&edge.to).unwrap()) {
             result.add_edge(edge.clone());
@@ -515,18 +923,18 @@ pub fn minimal_spanning_tree_kruskal(graph: &Graph) -> Edges {
 }
 
 #[derive(Clone, Copy)]
-pub enum Distance {
+pub enum Distance<W = u32> {
     Inf,
-    Dist(u32),
+    Dist(W),
 }
 
-impl Distance {
-    pub fn set_dist(&mut self, value: u32) {
+impl<W> Distance<W> {
+    pub fn set_dist(&mut self, value: W) {
         *self = Distance::Dist(value);
     }
 }
 
-impl Display for Distance {
+impl<W: Display> Display for Distance<W> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Distance::Inf => f.write_str("Inf"),
@@ -535,9 +943,9 @@ impl Display for Distance {
     }
 }
 
-impl Eq for Distance {}
+impl<W: Ord> Eq for Distance<W> {}
 
-impl Ord for Distance {
+impl<W: Ord> Ord for Distance<W> {
     fn cmp(&self, other: &Self) -> Ordering {
         match (self, other) {
             (Distance::Inf, Distance::Inf) => Ordering::Equal,
@@ -548,19 +956,19 @@ impl Ord for Distance {
     }
 }
 
-impl PartialEq<Self> for Distance {
+impl<W: Ord> PartialEq<Self> for Distance<W> {
     fn eq(&self, other: &Self) -> bool {
         self.cmp(other).is_eq()
     }
 }
 
-impl PartialOrd<Self> for Distance {
+impl<W: Ord> PartialOrd<Self> for Distance<W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Option::Some(self.cmp(other))
     }
 }
 
-impl Add for Distance {
+impl<W: Add<Output = W>> Add for Distance<W> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -573,14 +981,21 @@ impl Add for Distance {
 }
 
 pub fn minimal_spanning_tree_prim(graph: &Graph, root: u32) -> Edges {
+    minimal_spanning_tree_prim_by(graph, root, |value| value.unwrap_or(1))
+}
+
+pub fn minimal_spanning_tree_prim_by<N, E: Clone, W: Weight>(
+    graph: &Graph<N, E>,
+    root: u32,
+    weight: impl Fn(&E) -> W,
+) -> Edges<E> {
     let mut result = Edges::new();
-    let mut min_heap: PositionTrackingMinHeap<u32> = PositionTrackingMinHeap::new();
-    let mut prev = HashMap::new();
+    let mut min_heap: PositionTrackingMinHeap<u32, W> = PositionTrackingMinHeap::new();
+    let mut chosen: BTreeMap<u32, &Edge<E>> = BTreeMap::new();
     for (vert, _) in graph.adj.iter() {
         if *vert != root {
             min_heap.insert(VertAttributes::new_inf(*vert));
         }
-        prev.insert(*vert, *vert);
     }
     min_heap.insert(VertAttributes::new_zero(root));
     while !min_heap.is_empty() {
@@ -591,9 +1006,9 @@ pub fn minimal_spanning_tree_prim(graph: &Graph, root: u32) -> Edges {
             Edges::Edges(edges) => {
                 for edge in edges {
                     if let Some(index) = min_heap.vert_index(edge.to) {
-                        let dist = Distance::Dist(edge.value.unwrap_or(1));
+                        let dist = Distance::Dist(weight(&edge.value));
                         if dist < min_heap.get(index).unwrap().distance {
-                            prev.insert(edge.to, curr_vert);
+                            chosen.insert(edge.to, edge);
                             min_heap
                                 .heap_decrease_key(index, VertAttributes::new_dist(edge.to, dist));
                         }
@@ -602,22 +1017,79 @@ pub fn minimal_spanning_tree_prim(graph: &Graph, root: u32) -> Edges {
             }
         }
     }
-    for (from, to) in prev.iter() {
-        match graph.adj.get(from).unwrap_or(&Edges::Empty) {
-            Edges::Empty => {}
-            Edges::Edges(edges) => {
-                for edge in edges.iter() {
-                    if edge.to == *to {
-                        result.add_edge(edge.clone());
-                    }
-                }
+    for (_, edge) in chosen.iter() {
+        result.add_edge((*edge).clone());
+    }
+    result
+}
+
+pub struct Path<T = u32, S = Edge, W = u32> {
+    vertices: Vec<T>,
+    steps: Vec<S>,
+    cost: W,
+}
+
+impl<T, S, W: Copy> Path<T, S, W> {
+    fn new(vertices: Vec<T>, steps: Vec<S>, cost: W) -> Path<T, S, W> {
+        Path {
+            vertices,
+            steps,
+            cost,
+        }
+    }
+
+    pub fn vertices(&self) -> &Vec<T> {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> &Vec<S> {
+        &self.steps
+    }
+
+    pub fn cost(&self) -> W {
+        self.cost
+    }
+
+    pub fn hops(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn start(&self) -> Option<&T> {
+        self.vertices.first()
+    }
+
+    pub fn end(&self) -> Option<&T> {
+        self.vertices.last()
+    }
+}
+
+impl<E: Clone, W: Copy> Path<u32, Edge<E>, W> {
+    pub fn to_edges(&self) -> Edges<E> {
+        let mut result = Edges::new();
+        for edge in self.steps.iter() {
+            result.add_edge(edge.clone());
+        }
+        result
+    }
+}
+
+impl<T: Display, S, W: Display> Display for Path<T, S, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (x, vert) in self.vertices.iter().enumerate() {
+            if x == 0 {
+                f.write_str(format!("{}", vert).as_str())?;
+            } else {
+                f.write_str(format!(" -> {}", vert).as_str())?;
             }
         }
+        f.write_str(format!(" (cost: {}, hops: {})", self.cost, self.steps.len()).as_str())
     }
-    result
 }
 
-fn initialize_single_source_dijkstra(graph: &Graph, source: u32) -> PositionTrackingMinHeap<u32> {
+fn initialize_single_source_dijkstra<N, E, W: Weight>(
+    graph: &Graph<N, E>,
+    source: u32,
+) -> PositionTrackingMinHeap<u32, W> {
     let mut result = PositionTrackingMinHeap::new();
     for (vert, _) in graph.adj.iter() {
         if *vert != source {
@@ -630,8 +1102,11 @@ fn initialize_single_source_dijkstra(graph: &Graph, source: u32) -> PositionTrac
     result
 }
 
-fn initialize_single_source(graph: &Graph, source: u32) -> HashMap<u32, VertAttributes<u32>> {
-    let mut result = HashMap::new();
+fn initialize_single_source<N, E, W: Weight>(
+    graph: &Graph<N, E>,
+    source: u32,
+) -> BTreeMap<u32, VertAttributes<u32, W>> {
+    let mut result = BTreeMap::new();
     for (vert, _) in graph.adj.iter() {
         let attributes = VertAttributes::new_inf(*vert);
         result.insert(*vert, attributes);
@@ -641,13 +1116,14 @@ fn initialize_single_source(graph: &Graph, source: u32) -> HashMap<u32, VertAttr
     result
 }
 
-fn relax(
-    attributes: &HashMap<u32, VertAttributes<u32>>,
-    edge: &Edge,
-) -> Option<VertAttributes<u32>> {
+fn relax<E, W: Weight>(
+    attributes: &BTreeMap<u32, VertAttributes<u32, W>>,
+    edge: &Edge<E>,
+    weight: W,
+) -> Option<VertAttributes<u32, W>> {
     let from_distance = attributes.get(&edge.from).unwrap().distance;
     let to_distance = attributes.get(&edge.to).unwrap().distance;
-    let dist_through = from_distance + Distance::Dist(edge.value.unwrap_or(1));
+    let dist_through = from_distance + Distance::Dist(weight);
     let mut result = Option::None;
     if to_distance > dist_through {
         let new_value = VertAttributes {
@@ -660,27 +1136,42 @@ fn relax(
     result
 }
 
-fn recreate_path(start: u32, end: u32, predecessors: &HashMap<u32, u32>) -> Option<Edges> {
-    let mut result = Edges::new();
+fn recreate_path<N, E: Clone, W: Weight>(
+    graph: &Graph<N, E>,
+    start: u32,
+    end: u32,
+    predecessors: &HashMap<u32, u32>,
+    weight: impl Fn(&E) -> W,
+) -> Option<Path<u32, Edge<E>, W>> {
+    if !graph.contains_vertex(start) || !graph.contains_vertex(end) {
+        return Option::None;
+    }
+    let mut vertices = vec![end];
+    let mut steps = Vec::new();
+    let mut cost = W::zero();
     let mut curr = end;
-    let mut found_start = false;
-    while let Some(pred) = predecessors.get(&curr) {
-        result.add_edge(Edge::new(*pred, curr));
-        curr = *pred;
-        if *pred == start {
-            found_start = true;
-            break;
+    while curr != start {
+        let pred = *predecessors.get(&curr)?;
+        let edge = graph
+            .edges_from(pred)
+            .filter(|edge| edge.to == curr)
+            .min_by_key(|edge| weight(&edge.value))?;
+        cost = cost + weight(&edge.value);
+        steps.push(edge.clone());
+        vertices.push(pred);
+        curr = pred;
+        if steps.len() > graph.adj.len() {
+            return Option::None;
         }
     }
-    result.reverse();
-    if found_start {
-        Option::Some(result)
-    } else {
-        Option::None
-    }
+    vertices.reverse();
+    steps.reverse();
+    Option::Some(Path::new(vertices, steps, cost))
 }
 
-fn attributes_to_predecessors(attributes: &HashMap<u32, VertAttributes<u32>>) -> HashMap<u32, u32> {
+fn attributes_to_predecessors<W: Copy + Ord>(
+    attributes: &BTreeMap<u32, VertAttributes<u32, W>>,
+) -> HashMap<u32, u32> {
     let mut result = HashMap::new();
     for (vert, attr) in attributes.iter() {
         match attr.predecessor {
@@ -693,21 +1184,40 @@ fn attributes_to_predecessors(attributes: &HashMap<u32, VertAttributes<u32>>) ->
     result
 }
 
-pub fn shortest_path_bellman_ford(graph: &Graph, start: u32, end: u32) -> Option<Edges> {
+pub fn shortest_path_bellman_ford(graph: &Graph, start: u32, end: u32) -> Option<Path> {
+    shortest_path_bellman_ford_by(graph, start, end, |value| value.unwrap_or(1))
+}
+
+pub fn shortest_path_bellman_ford_by<N, E: Clone, W: Weight>(
+    graph: &Graph<N, E>,
+    start: u32,
+    end: u32,
+    weight: impl Fn(&E) -> W,
+) -> Option<Path<u32, Edge<E>, W>> {
     let mut attributes = initialize_single_source(graph, start);
     for _ in 0..graph.adj.len() {
         for (_, edges) in graph.adj.iter() {
             for edge in edges.iter() {
-                if let Some(new_attribute) = relax(&attributes, edge) {
+                if let Some(new_attribute) = relax(&attributes, edge, weight(&edge.value)) {
                     attributes.insert(edge.to, new_attribute);
                 }
             }
         }
     }
-    recreate_path(start, end, &attributes_to_predecessors(&attributes))
+    let predecessors = attributes_to_predecessors(&attributes);
+    recreate_path(graph, start, end, &predecessors, weight)
 }
 
-pub fn shortest_path_dijkstra(graph: &Graph, start: u32, end: u32) -> Option<Edges> {
+pub fn shortest_path_dijkstra(graph: &Graph, start: u32, end: u32) -> Option<Path> {
+    shortest_path_dijkstra_by(graph, start, end, |value| value.unwrap_or(1))
+}
+
+pub fn shortest_path_dijkstra_by<N, E: Clone, W: Weight>(
+    graph: &Graph<N, E>,
+    start: u32,
+    end: u32,
+    weight: impl Fn(&E) -> W,
+) -> Option<Path<u32, Edge<E>, W>> {
     let mut attributes = initialize_single_source(graph, start);
     let mut heap = PositionTrackingMinHeap::new();
     for (_, attr) in attributes.iter() {
@@ -716,7 +1226,7 @@ pub fn shortest_path_dijkstra(graph: &Graph, start: u32, end: u32) -> Option<Edg
     while !heap.is_empty() {
         let vert_attr = heap.extract_min();
         for edge in graph.adj.get(&vert_attr.id).unwrap().iter() {
-            if let Some(new_attribute) = relax(&mut attributes, edge) {
+            if let Some(new_attribute) = relax(&mut attributes, edge, weight(&edge.value)) {
                 if let Some(idx) = heap.vert_index(edge.to) {
                     attributes.insert(edge.to, new_attribute);
                     heap.heap_decrease_key(idx, new_attribute);
@@ -724,7 +1234,8 @@ pub fn shortest_path_dijkstra(graph: &Graph, start: u32, end: u32) -> Option<Edg
             }
         }
     }
-    recreate_path(start, end, &attributes_to_predecessors(&attributes))
+    let predecessors = attributes_to_predecessors(&attributes);
+    recreate_path(graph, start, end, &predecessors, weight)
 }
 
 enum MapSlice {
@@ -759,7 +1270,7 @@ pub struct TerrainMap {
     height: u32,
 }
 
-#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Point2d(u32, u32);
 
 impl Point2d {
@@ -891,28 +1402,29 @@ impl Display for TerrainMap {
 }
 
 fn a_star_recreate_path(
-    attributes: &HashMap<Point2d, VertAttributes<Point2d>>,
+    graph: &TerrainMap,
+    attributes: &BTreeMap<Point2d, VertAttributes<Point2d>>,
     start: &Point2d,
     end: &Point2d,
-) -> Option<Vec<Point2d>> {
-    let mut result = Vec::new();
-    let mut found_start = false;
-    let mut curr = attributes.get(end).unwrap();
-    result.push(*end);
-    while let Some(pred) = curr.predecessor {
-        result.push(pred.clone());
-        if pred == *start {
-            found_start = true;
-            break;
-        }
+) -> Option<Path<Point2d, u32>> {
+    let mut vertices = vec![*end];
+    let mut steps = Vec::new();
+    let mut cost = 0;
+    let mut curr = attributes.get(end)?;
+    while curr.id != *start {
+        let pred = curr.predecessor?;
+        let step = match graph.distance(&pred, &curr.id)? {
+            Distance::Dist(step) => step,
+            Distance::Inf => return Option::None,
+        };
+        cost += step;
+        steps.push(step);
+        vertices.push(pred);
         curr = attributes.get(&pred).unwrap();
     }
-    result.reverse();
-    if found_start {
-        Option::Some(result)
-    } else {
-        Option::None
-    }
+    vertices.reverse();
+    steps.reverse();
+    Option::Some(Path::new(vertices, steps, cost))
 }
 
 pub fn shortest_path_a_star(
@@ -920,9 +1432,9 @@ pub fn shortest_path_a_star(
     start: Point2d,
     end: Point2d,
     heuristic: impl Fn(&Point2d, &Point2d) -> Distance,
-) -> Option<Vec<Point2d>> {
+) -> Option<Path<Point2d, u32>> {
     let mut real_dist: HashMap<Point2d, Distance> = HashMap::new();
-    let mut attributes = HashMap::new();
+    let mut attributes = BTreeMap::new();
     let mut heap: PositionTrackingMinHeap<Point2d> = PositionTrackingMinHeap::new();
     for row in 0..graph.height {
         for column in 0..graph.width {
@@ -945,8 +1457,8 @@ pub fn shortest_path_a_star(
             break;
         }
         for neighbor in graph.neighbors(&min_vert.id).iter() {
-            let tentative_score =
-                min_vert.distance + graph.distance(&min_vert.id, neighbor).unwrap();
+            let tentative_score = *real_dist.get(&min_vert.id).unwrap()
+                + graph.distance(&min_vert.id, neighbor).unwrap();
             if tentative_score < *real_dist.get(neighbor).unwrap() {
                 let new_attr = VertAttributes {
                     id: neighbor.clone(),
@@ -961,7 +1473,7 @@ pub fn shortest_path_a_star(
             }
         }
     }
-    a_star_recreate_path(&attributes, &start, &end)
+    a_star_recreate_path(graph, &attributes, &start, &end)
 }
 
 pub fn mark_path(graph: &mut TerrainMap, path: &Vec<Point2d>) {
@@ -971,3 +1483,260 @@ pub fn mark_path(graph: &mut TerrainMap, path: &Vec<Point2d>) {
         }
     }
 }
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use crate::graph::{Edge, Graph};
+    use rand::rngs::StdRng;
+    use rand::Rng;
+    use std::ops::Range;
+
+    pub(crate) fn directed(edges: &[(u32, u32)]) -> Graph {
+        let mut graph = Graph::new();
+        for (from, to) in edges.iter() {
+            graph.add_adj(Edge::new(*from, *to));
+        }
+        graph
+    }
+
+    pub(crate) fn undirected(edges: &[(u32, u32)]) -> Graph {
+        let mut graph = Graph::new();
+        for (from, to) in edges.iter() {
+            graph.add_adj_both(Edge::new(*from, *to));
+        }
+        graph
+    }
+
+    pub(crate) fn directed_weighted(edges: &[(u32, u32, u32)]) -> Graph {
+        let mut graph = Graph::new();
+        for (from, to, weight) in edges.iter() {
+            graph.add_adj(Edge::new_weighted(*weight, *from, *to));
+        }
+        graph
+    }
+
+    pub(crate) fn undirected_weighted(edges: &[(u32, u32, u32)]) -> Graph {
+        let mut graph = Graph::new();
+        for (from, to, weight) in edges.iter() {
+            graph.add_adj_both(Edge::new_weighted(*weight, *from, *to));
+        }
+        graph
+    }
+
+    pub(crate) fn random_multigraph(
+        rng: &mut StdRng,
+        vertices: u32,
+        edges: usize,
+        weights: Range<u32>,
+        both: bool,
+    ) -> Graph {
+        let mut graph = Graph::new();
+        for vert in 0..vertices {
+            graph.add_vertex(vert);
+        }
+        for _ in 0..edges {
+            let from = rng.gen_range(0..vertices);
+            let to = rng.gen_range(0..vertices);
+            let edge = Edge::new_weighted(rng.gen_range(weights.clone()), from, to);
+            if both {
+                graph.add_adj_both(edge);
+            } else {
+                graph.add_adj(edge);
+            }
+        }
+        graph
+    }
+
+    pub(crate) fn random_simple_graph(rng: &mut StdRng, vertices: u32, probability: f64) -> Graph {
+        let mut graph = Graph::new();
+        for vert in 0..vertices {
+            graph.add_vertex(vert);
+        }
+        for first in 0..vertices {
+            for second in first + 1..vertices {
+                if rng.gen_bool(probability) {
+                    graph.add_adj_both(Edge::new(first, second));
+                }
+            }
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vertex_payloads_are_stored_and_mutable() {
+        let mut graph: Graph<&str, f64> = Graph::new();
+        assert_eq!(graph.insert_vertex(1, "start"), Option::None);
+        assert_eq!(graph.insert_vertex(1, "entry"), Option::Some("start"));
+        graph.add_edge(1, 2, 0.5);
+        assert_eq!(graph.vertex(1), Option::Some(&"entry"));
+        assert_eq!(graph.vertex(2), Option::Some(&""));
+        *graph.vertex_mut(2).unwrap() = "exit";
+        assert_eq!(graph.vertex(2), Option::Some(&"exit"));
+        assert_eq!(graph.edge(1, 2), Option::Some(&0.5));
+        assert_eq!(graph.vertex(3), Option::None);
+    }
+
+    #[test]
+    fn weighted_searches_use_the_weight_closure() {
+        let mut graph: Graph<(), (u64, &str)> = Graph::new();
+        graph.add_edge(1, 2, (1, "fast"));
+        graph.add_edge(2, 3, (1, "fast"));
+        graph.add_edge(1, 3, (5, "slow"));
+        let path = shortest_path_dijkstra_by(&graph, 1, 3, |value| value.0).unwrap();
+        assert_eq!(path.vertices(), &vec![1, 2, 3]);
+        assert_eq!(path.cost(), 2);
+        let path = shortest_path_bellman_ford_by(&graph, 1, 3, |_| 1u64).unwrap();
+        assert_eq!(path.vertices(), &vec![1, 3]);
+        assert_eq!(path.cost(), 1);
+        let tree = minimal_spanning_tree_kruskal_by(&graph, |value| value.0);
+        assert_eq!(tree.len(), 2);
+        assert!(tree.iter().all(|edge| edge.value().1 == "fast"));
+    }
+
+    #[test]
+    fn shortest_paths_carry_edges_cost_and_hops() {
+        let mut graph = fixtures::directed_weighted(&[(1, 2, 2), (2, 4, 3), (1, 4, 9), (4, 5, 1)]);
+        graph.add_vertex(6);
+        for path in [
+            shortest_path_dijkstra(&graph, 1, 5).unwrap(),
+            shortest_path_bellman_ford(&graph, 1, 5).unwrap(),
+        ]
+        .iter()
+        {
+            assert_eq!(path.vertices(), &vec![1, 2, 4, 5]);
+            let weights: Vec<Option<u32>> = path.edges().iter().map(|e| *e.value()).collect();
+            assert_eq!(
+                weights,
+                vec![Option::Some(2), Option::Some(3), Option::Some(1)]
+            );
+            assert_eq!(path.cost(), 6);
+            assert_eq!(path.hops(), 3);
+            assert_eq!(path.start(), Option::Some(&1));
+            assert_eq!(path.end(), Option::Some(&5));
+            assert_eq!(path.to_edges().len(), 3);
+            assert_eq!(path.to_string(), "1 -> 2 -> 4 -> 5 (cost: 6, hops: 3)");
+        }
+        let trivial = shortest_path_dijkstra(&graph, 1, 1).unwrap();
+        assert_eq!(trivial.to_string(), "1 (cost: 0, hops: 0)");
+        assert!(shortest_path_dijkstra(&graph, 1, 6).is_none());
+        assert!(shortest_path_bellman_ford(&graph, 5, 1).is_none());
+    }
+
+    #[test]
+    fn a_star_walks_around_obstacles() {
+        let mut map = TerrainMap::new(4, 4);
+        map.put_obstacle(Point2d::new(1, 0), Point2d::new(1, 2));
+        let flat = |_: &Point2d, _: &Point2d| Distance::Dist(0);
+        let path =
+            shortest_path_a_star(&map, Point2d::new(0, 0), Point2d::new(2, 0), flat).unwrap();
+        assert_eq!(path.hops(), 8);
+        assert_eq!(path.cost(), 8);
+        assert!(path.edges().iter().all(|step| *step == 1));
+        assert!(path.start() == Option::Some(&Point2d::new(0, 0)));
+        assert!(path.end() == Option::Some(&Point2d::new(2, 0)));
+        assert!(path.to_string().ends_with("(2,0) (cost: 8, hops: 8)"));
+        map.put_obstacle(Point2d::new(1, 3), Point2d::new(1, 3));
+        assert!(shortest_path_a_star(&map, Point2d::new(0, 0), Point2d::new(2, 0), flat).is_none());
+    }
+
+    #[test]
+    fn traversal_order_is_reproducible() {
+        let edges = [(5, 1), (1, 3), (3, 2), (2, 5), (6, 4), (1, 2)];
+        let build = || {
+            let mut graph: Graph = Graph::new();
+            for (from, to) in edges.iter() {
+                graph.add_adj_both(Edge::new(*from, *to));
+            }
+            graph
+        };
+        let graph = build();
+        assert_eq!(graph.vertices(), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(depth_first_search(&graph).to_string(), "[1,5,2,3,4,6]");
+        assert_eq!(breadth_first_search(&graph, 1).to_string(), "[1,5,3,2]");
+        for _ in 0..5 {
+            let again = build();
+            assert_eq!(
+                depth_first_search(&again).to_string(),
+                depth_first_search(&graph).to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn neighbour_sets_are_symmetric_and_skip_self_loops() {
+        let mut graph: Graph = Graph::new();
+        graph.add_adj(Edge::new(1, 2));
+        graph.add_adj(Edge::new(2, 2));
+        graph.add_adj_both(Edge::new(2, 3));
+        graph.add_vertex(4);
+        let neighbours = graph.neighbour_sets();
+        assert_eq!(neighbours[&1].iter().copied().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(
+            neighbours[&2].iter().copied().collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert_eq!(neighbours[&3].iter().copied().collect::<Vec<_>>(), vec![2]);
+        assert!(neighbours[&4].is_empty());
+    }
+
+    #[test]
+    fn undirected_edges_are_counted_once() {
+        let mut graph: Graph = Graph::new();
+        graph.add_adj_both(Edge::new(1, 2));
+        graph.add_adj_both(Edge::new(3, 3));
+        graph.add_adj(Edge::new(2, 3));
+        graph.add_adj(Edge::new(4, 4));
+        assert_eq!(graph.edge_count(), 4);
+        let id = graph.edges_from(1).next().unwrap().id().unwrap();
+        graph.remove_edge(id);
+        assert_eq!(graph.edge_count(), 3);
+    }
+
+    #[test]
+    fn set_edge_value_updates_both_halves() {
+        let mut graph: Graph = Graph::new();
+        let undirected = graph.add_adj_both(Edge::new_weighted(4, 1, 2)).unwrap();
+        let directed = graph.add_adj(Edge::new_weighted(4, 2, 3)).unwrap();
+        graph.set_edge_value(directed, Option::Some(9));
+        assert_eq!(graph.edge(2, 3), Option::Some(&Option::Some(9)));
+        assert_eq!(graph.edge(3, 2), Option::None);
+        graph.set_edge_value(undirected, Option::Some(7));
+        assert_eq!(graph.edge(1, 2), Option::Some(&Option::Some(7)));
+        assert_eq!(graph.edge(2, 1), Option::Some(&Option::Some(7)));
+    }
+
+    #[test]
+    fn parallel_edge_policies() {
+        let mut graph: Graph = Graph::with_policy(ParallelEdgePolicy::sum_weights());
+        let first = graph.add_adj_both(Edge::new_weighted(u32::MAX - 1, 1, 2));
+        let second = graph.add_adj(Edge::new_weighted(5, 1, 2));
+        assert!(first == second);
+        assert_eq!(graph.edge(1, 2), Option::Some(&Option::Some(u32::MAX)));
+        assert_eq!(graph.edge(2, 1), Option::Some(&Option::Some(u32::MAX)));
+        assert_eq!(graph.edge_count(), 1);
+
+        let mut graph: Graph = Graph::with_policy(ParallelEdgePolicy::keep_minimum());
+        graph.add_adj(Edge::new_weighted(5, 1, 2));
+        graph.add_adj(Edge::new_weighted(3, 1, 2));
+        graph.add_adj(Edge::new_weighted(8, 1, 2));
+        assert_eq!(graph.edges_between(1, 2).len(), 1);
+        assert_eq!(graph.edge(1, 2), Option::Some(&Option::Some(3)));
+
+        let mut graph: Graph = Graph::with_policy(ParallelEdgePolicy::RejectDuplicates);
+        assert!(graph.add_adj(Edge::new(1, 2)).is_some());
+        assert!(graph.add_adj(Edge::new(1, 2)).is_none());
+        assert!(graph.add_adj(Edge::new(2, 1)).is_some());
+
+        let mut graph: Graph = Graph::new();
+        let first = graph.add_adj(Edge::new(1, 2)).unwrap();
+        let second = graph.add_adj(Edge::new(1, 2)).unwrap();
+        assert!(first != second);
+        assert_eq!(graph.edge_by_id(second).unwrap().to(), 2);
+        assert_eq!(graph.edge_count(), 2);
+    }
+}
@@ -0,0 +1,350 @@
+use crate::graph::Graph;
+use std::collections::{BTreeSet, HashSet};
+
+fn self_loops<N, E>(graph: &Graph<N, E>) -> HashSet<u32> {
+    graph
+        .adj
+        .iter()
+        .filter(|(vert, edges)| edges.iter().any(|e| e.to == **vert))
+        .map(|(vert, _)| *vert)
+        .collect()
+}
+
+pub fn vertex_cover_2_approx<N, E>(graph: &Graph<N, E>) -> Vec<u32> {
+    let mut covered: BTreeSet<u32> = self_loops(graph).into_iter().collect();
+    for (vert, edges) in graph.adj.iter() {
+        for edge in edges.iter() {
+            if !covered.contains(vert) && !covered.contains(&edge.to) {
+                covered.insert(*vert);
+                covered.insert(edge.to);
+            }
+        }
+    }
+    covered.into_iter().collect()
+}
+
+pub fn greedy_independent_set<N, E>(graph: &Graph<N, E>) -> Vec<u32> {
+    let neighbours = graph.neighbour_sets();
+    let loops = self_loops(graph);
+    let mut remaining: BTreeSet<u32> = neighbours
+        .keys()
+        .filter(|v| !loops.contains(v))
+        .copied()
+        .collect();
+    let mut result = Vec::new();
+    while let Some(vert) = remaining
+        .iter()
+        .copied()
+        .min_by_key(|v| neighbours[v].intersection(&remaining).count())
+    {
+        result.push(vert);
+        remaining.remove(&vert);
+        for other in neighbours[&vert].iter() {
+            remaining.remove(other);
+        }
+    }
+    result.sort_unstable();
+    result
+}
+
+pub fn greedy_dominating_set<N, E>(graph: &Graph<N, E>) -> Vec<u32> {
+    let neighbours = graph.neighbour_sets();
+    let mut undominated: BTreeSet<u32> = neighbours.keys().copied().collect();
+    let mut result = Vec::new();
+    while !undominated.is_empty() {
+        let vert = neighbours
+            .iter()
+            .max_by_key(|(vert, adjacent)| {
+                let gain = adjacent.intersection(&undominated).count()
+                    + undominated.contains(vert) as usize;
+                (gain, std::cmp::Reverse(**vert))
+            })
+            .map(|(vert, _)| *vert)
+            .unwrap();
+        result.push(vert);
+        undominated.remove(&vert);
+        for other in neighbours[&vert].iter() {
+            undominated.remove(other);
+        }
+    }
+    result.sort_unstable();
+    result
+}
+
+pub fn greedy_set_cover(universe: &[u32], sets: &[Vec<u32>]) -> Option<Vec<usize>> {
+    let mut uncovered: BTreeSet<u32> = universe.iter().copied().collect();
+    let mut result = Vec::new();
+    while !uncovered.is_empty() {
+        let (best, gain) = sets
+            .iter()
+            .enumerate()
+            .map(|(idx, set)| (idx, set.iter().filter(|e| uncovered.contains(e)).count()))
+            .max_by_key(|(idx, gain)| (*gain, std::cmp::Reverse(*idx)))?;
+        if gain == 0 {
+            return Option::None;
+        }
+        for element in sets[best].iter() {
+            uncovered.remove(element);
+        }
+        result.push(best);
+    }
+    Option::Some(result)
+}
+
+const EXACT_MAX_VERTICES: usize = 64;
+
+struct BitGraph {
+    vertices: Vec<u32>,
+    closed: Vec<u64>,
+    loops: u64,
+}
+
+impl BitGraph {
+    fn new<N, E>(graph: &Graph<N, E>) -> Option<BitGraph> {
+        let vertices = graph.vertices();
+        if vertices.len() > EXACT_MAX_VERTICES {
+            return Option::None;
+        }
+        let neighbours = graph.neighbour_sets();
+        let position = |vert: &u32| vertices.binary_search(vert).unwrap();
+        let closed = vertices
+            .iter()
+            .enumerate()
+            .map(|(idx, vert)| {
+                neighbours[vert]
+                    .iter()
+                    .fold(1u64 << idx, |mask, other| mask | 1u64 << position(other))
+            })
+            .collect();
+        let loops = self_loops(graph)
+            .iter()
+            .fold(0u64, |mask, vert| mask | 1u64 << position(vert));
+        Option::Some(BitGraph {
+            vertices,
+            closed,
+            loops,
+        })
+    }
+
+    fn all(&self) -> u64 {
+        if self.vertices.len() == EXACT_MAX_VERTICES {
+            u64::MAX
+        } else {
+            (1u64 << self.vertices.len()) - 1
+        }
+    }
+
+    fn to_vertices(&self, mask: u64) -> Vec<u32> {
+        (0..self.vertices.len())
+            .filter(|idx| mask & (1u64 << idx) != 0)
+            .map(|idx| self.vertices[idx])
+            .collect()
+    }
+
+    fn independent(&self, candidates: u64, chosen: u64, best: &mut u64) {
+        if candidates == 0 {
+            if chosen.count_ones() > best.count_ones() {
+                *best = chosen;
+            }
+            return;
+        }
+        if chosen.count_ones() + candidates.count_ones() <= best.count_ones() {
+            return;
+        }
+        let vert = candidates.trailing_zeros() as usize;
+        let bit = 1u64 << vert;
+        self.independent(candidates & !self.closed[vert], chosen | bit, best);
+        if self.closed[vert] & candidates != bit {
+            self.independent(candidates & !bit, chosen, best);
+        }
+    }
+
+    fn dominating(&self, undominated: u64, chosen: u64, best: &mut u64) {
+        if undominated == 0 {
+            if chosen.count_ones() < best.count_ones() {
+                *best = chosen;
+            }
+            return;
+        }
+        if chosen.count_ones() + 1 >= best.count_ones() {
+            return;
+        }
+        let vert = undominated.trailing_zeros() as usize;
+        let mut options = self.closed[vert];
+        while options != 0 {
+            let other = options.trailing_zeros() as usize;
+            options &= options - 1;
+            self.dominating(
+                undominated & !self.closed[other],
+                chosen | (1u64 << other),
+                best,
+            );
+        }
+    }
+}
+
+pub fn maximum_independent_set_exact<N, E>(graph: &Graph<N, E>) -> Option<Vec<u32>> {
+    let bits = BitGraph::new(graph)?;
+    let mut best = 0;
+    bits.independent(bits.all() & !bits.loops, 0, &mut best);
+    Option::Some(bits.to_vertices(best))
+}
+
+pub fn minimum_vertex_cover_exact<N, E>(graph: &Graph<N, E>) -> Option<Vec<u32>> {
+    let bits = BitGraph::new(graph)?;
+    let mut best = 0;
+    bits.independent(bits.all() & !bits.loops, 0, &mut best);
+    Option::Some(bits.to_vertices(bits.all() & !best))
+}
+
+pub fn minimum_dominating_set_exact<N, E>(graph: &Graph<N, E>) -> Option<Vec<u32>> {
+    let bits = BitGraph::new(graph)?;
+    let mut best = bits.all();
+    bits.dominating(bits.all(), 0, &mut best);
+    Option::Some(bits.to_vertices(best))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::undirected;
+    use crate::graph::Edge;
+
+    fn petersen() -> Graph {
+        let mut edges = Vec::new();
+        for i in 0..5 {
+            edges.push((i, (i + 1) % 5));
+            edges.push((i, i + 5));
+            edges.push((i + 5, (i + 2) % 5 + 5));
+        }
+        undirected(&edges)
+    }
+
+    fn star(leaves: u32) -> Graph {
+        let edges: Vec<(u32, u32)> = (1..=leaves).map(|leaf| (0, leaf)).collect();
+        undirected(&edges)
+    }
+
+    fn looped_path() -> Graph {
+        let mut graph = undirected(&[(1, 2), (2, 3), (3, 4)]);
+        graph.add_adj(Edge::new(2, 2));
+        graph.add_adj_both(Edge::new(4, 4));
+        graph
+    }
+
+    fn is_cover(graph: &Graph, cover: &[u32]) -> bool {
+        graph.adj.iter().all(|(vert, edges)| {
+            edges
+                .iter()
+                .all(|edge| cover.contains(vert) || cover.contains(&edge.to))
+        })
+    }
+
+    fn is_independent(graph: &Graph, set: &[u32]) -> bool {
+        set.iter()
+            .all(|vert| graph.edges_from(*vert).all(|edge| !set.contains(&edge.to)))
+    }
+
+    fn is_dominating(graph: &Graph, set: &[u32]) -> bool {
+        let neighbours = graph.neighbour_sets();
+        neighbours
+            .iter()
+            .all(|(vert, adjacent)| set.contains(vert) || adjacent.iter().any(|v| set.contains(v)))
+    }
+
+    #[test]
+    fn vertex_cover_is_within_twice_the_optimum() {
+        for graph in [petersen(), star(6), looped_path()].iter() {
+            let approx = vertex_cover_2_approx(graph);
+            let exact = minimum_vertex_cover_exact(graph).unwrap();
+            assert!(is_cover(graph, &approx));
+            assert!(is_cover(graph, &exact));
+            assert!(exact.len() <= approx.len());
+            assert!(approx.len() <= 2 * exact.len());
+        }
+        assert_eq!(minimum_vertex_cover_exact(&petersen()).unwrap().len(), 6);
+        assert_eq!(minimum_vertex_cover_exact(&star(6)).unwrap(), vec![0]);
+        assert_eq!(
+            minimum_vertex_cover_exact(&looped_path()).unwrap(),
+            vec![2, 4]
+        );
+    }
+
+    #[test]
+    fn independent_set_is_maximal_and_bounded_by_the_optimum() {
+        for graph in [petersen(), star(6), looped_path()].iter() {
+            let greedy = greedy_independent_set(graph);
+            let exact = maximum_independent_set_exact(graph).unwrap();
+            assert!(is_independent(graph, &greedy));
+            assert!(is_independent(graph, &exact));
+            assert!(greedy.len() <= exact.len());
+            let loops = self_loops(graph);
+            for vert in graph.adj.keys() {
+                if greedy.contains(vert) || loops.contains(vert) {
+                    continue;
+                }
+                assert!(!is_independent(
+                    graph,
+                    &[greedy.clone(), vec![*vert]].concat()
+                ));
+            }
+        }
+        assert_eq!(maximum_independent_set_exact(&petersen()).unwrap().len(), 4);
+        assert_eq!(greedy_independent_set(&star(6)), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            maximum_independent_set_exact(&looped_path()).unwrap(),
+            vec![1, 3]
+        );
+        assert_eq!(greedy_independent_set(&looped_path()), vec![1, 3]);
+    }
+
+    #[test]
+    fn dominating_set_is_within_the_greedy_bound() {
+        for graph in [petersen(), star(6), looped_path()].iter() {
+            let greedy = greedy_dominating_set(graph);
+            let exact = minimum_dominating_set_exact(graph).unwrap();
+            assert!(is_dominating(graph, &greedy));
+            assert!(is_dominating(graph, &exact));
+            assert!(exact.len() <= greedy.len());
+            let max_degree = graph
+                .neighbour_sets()
+                .values()
+                .map(|n| n.len())
+                .max()
+                .unwrap();
+            let bound = (1.0 + ((max_degree + 1) as f64).ln()) * exact.len() as f64;
+            assert!(greedy.len() as f64 <= bound);
+        }
+        assert_eq!(minimum_dominating_set_exact(&petersen()).unwrap().len(), 3);
+        assert_eq!(greedy_dominating_set(&star(6)), vec![0]);
+        assert_eq!(minimum_dominating_set_exact(&star(6)).unwrap(), vec![0]);
+        assert_eq!(
+            minimum_dominating_set_exact(&looped_path()).unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn exact_solvers_decline_graphs_beyond_the_limit() {
+        let mut graph: Graph = Graph::new();
+        for vert in 0..EXACT_MAX_VERTICES as u32 {
+            graph.add_vertex(vert);
+        }
+        assert_eq!(maximum_independent_set_exact(&graph).unwrap().len(), 64);
+        assert!(minimum_vertex_cover_exact(&graph).unwrap().is_empty());
+        assert_eq!(minimum_dominating_set_exact(&graph).unwrap().len(), 64);
+        graph.add_vertex(64);
+        assert!(maximum_independent_set_exact(&graph).is_none());
+        assert!(minimum_vertex_cover_exact(&graph).is_none());
+        assert!(minimum_dominating_set_exact(&graph).is_none());
+    }
+
+    #[test]
+    fn set_cover_picks_the_largest_gain_first() {
+        let universe = [1, 2, 3, 4, 5, 6];
+        let sets = vec![vec![1, 2, 3, 4], vec![1, 5], vec![4, 6], vec![5, 6]];
+        assert_eq!(greedy_set_cover(&universe, &sets), Option::Some(vec![0, 3]));
+        assert_eq!(greedy_set_cover(&[1, 7], &sets), Option::None);
+        assert_eq!(greedy_set_cover(&[], &sets), Option::Some(Vec::new()));
+    }
+}
@@ -0,0 +1,413 @@
+use crate::graph::{Edge, Edges, Graph};
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryFrom;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ArborescenceError {
+    MissingRoot,
+    Unreachable(u32),
+    WeightTooLarge(u64),
+}
+
+pub struct Arborescence<E = Option<u32>> {
+    root: u32,
+    edges: Edges<E>,
+    cost: u64,
+}
+
+impl<E> Arborescence<E> {
+    pub fn root(&self) -> u32 {
+        self.root
+    }
+
+    pub fn edges(&self) -> &Edges<E> {
+        &self.edges
+    }
+
+    pub fn cost(&self) -> u64 {
+        self.cost
+    }
+}
+
+struct HeapNode {
+    edge: usize,
+    weight: i64,
+    delta: i64,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+struct SkewHeap {
+    nodes: Vec<HeapNode>,
+}
+
+impl SkewHeap {
+    fn push(&mut self, edge: usize, weight: i64) -> usize {
+        self.nodes.push(HeapNode {
+            edge,
+            weight,
+            delta: 0,
+            left: Option::None,
+            right: Option::None,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn push_down(&mut self, node: usize) {
+        let delta = self.nodes[node].delta;
+        if delta == 0 {
+            return;
+        }
+        self.nodes[node].weight += delta;
+        self.nodes[node].delta = 0;
+        if let Some(left) = self.nodes[node].left {
+            self.nodes[left].delta += delta;
+        }
+        if let Some(right) = self.nodes[node].right {
+            self.nodes[right].delta += delta;
+        }
+    }
+
+    fn merge(&mut self, first: Option<usize>, second: Option<usize>) -> Option<usize> {
+        match (first, second) {
+            (None, other) | (other, None) => other,
+            (Some(mut first), Some(mut second)) => {
+                self.push_down(first);
+                self.push_down(second);
+                if self.nodes[first].weight > self.nodes[second].weight {
+                    std::mem::swap(&mut first, &mut second);
+                }
+                let merged = self.merge(Option::Some(second), self.nodes[first].right);
+                self.nodes[first].right = self.nodes[first].left;
+                self.nodes[first].left = merged;
+                Option::Some(first)
+            }
+        }
+    }
+
+    fn top(&mut self, node: usize) -> (usize, i64) {
+        self.push_down(node);
+        (self.nodes[node].edge, self.nodes[node].weight)
+    }
+
+    fn pop(&mut self, node: usize) -> Option<usize> {
+        self.push_down(node);
+        self.merge(self.nodes[node].left, self.nodes[node].right)
+    }
+}
+
+struct RollbackUnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    history: Vec<(usize, usize)>,
+}
+
+impl RollbackUnionFind {
+    fn new(len: usize) -> RollbackUnionFind {
+        RollbackUnionFind {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            history: Vec::new(),
+        }
+    }
+
+    fn find(&self, vert: usize) -> usize {
+        let mut curr = vert;
+        while self.parent[curr] != curr {
+            curr = self.parent[curr];
+        }
+        curr
+    }
+
+    fn join(&mut self, first: usize, second: usize) -> bool {
+        let mut first = self.find(first);
+        let mut second = self.find(second);
+        if first == second {
+            return false;
+        }
+        if self.size[first] < self.size[second] {
+            std::mem::swap(&mut first, &mut second);
+        }
+        self.parent[second] = first;
+        self.size[first] += self.size[second];
+        self.history.push((second, first));
+        true
+    }
+
+    fn time(&self) -> usize {
+        self.history.len()
+    }
+
+    fn rollback(&mut self, time: usize) {
+        while self.history.len() > time {
+            let (child, parent) = self.history.pop().unwrap();
+            self.parent[child] = child;
+            self.size[parent] -= self.size[child];
+        }
+    }
+}
+
+fn first_unreachable<N, E>(graph: &Graph<N, E>, root: u32) -> Option<u32> {
+    let mut visited = vec![root];
+    let mut seen: HashMap<u32, bool> = graph.adj.keys().map(|v| (*v, false)).collect();
+    seen.insert(root, true);
+    while let Some(vert) = visited.pop() {
+        for edge in graph.edges_from(vert) {
+            if !seen[&edge.to] {
+                seen.insert(edge.to, true);
+                visited.push(edge.to);
+            }
+        }
+    }
+    graph.adj.keys().copied().find(|v| !seen[v])
+}
+
+pub fn minimum_arborescence(graph: &Graph, root: u32) -> Result<Arborescence, ArborescenceError> {
+    minimum_arborescence_by(graph, root, |value| value.unwrap_or(1) as u64)
+}
+
+pub fn minimum_arborescence_by<N, E: Clone>(
+    graph: &Graph<N, E>,
+    root: u32,
+    weight: impl Fn(&E) -> u64,
+) -> Result<Arborescence<E>, ArborescenceError> {
+    if !graph.contains_vertex(root) {
+        return Result::Err(ArborescenceError::MissingRoot);
+    }
+    if let Some(vert) = first_unreachable(graph, root) {
+        return Result::Err(ArborescenceError::Unreachable(vert));
+    }
+    let vertices = graph.vertices();
+    let index: HashMap<u32, usize> = vertices
+        .iter()
+        .enumerate()
+        .map(|(idx, vert)| (*vert, idx))
+        .collect();
+    let edges: Vec<&Edge<E>> = graph
+        .adj
+        .values()
+        .flat_map(|edges| edges.iter())
+        .filter(|edge| edge.from != edge.to)
+        .collect();
+    let size = vertices.len();
+    let mut heap = SkewHeap { nodes: Vec::new() };
+    let mut heads: Vec<Option<usize>> = vec![Option::None; size];
+    for (idx, edge) in edges.iter().enumerate() {
+        let to = index[&edge.to];
+        let value = weight(&edge.value);
+        let value = i64::try_from(value).map_err(|_| ArborescenceError::WeightTooLarge(value))?;
+        let node = heap.push(idx, value);
+        heads[to] = heap.merge(heads[to], Option::Some(node));
+    }
+    let mut components = RollbackUnionFind::new(size);
+    let mut seen: Vec<Option<usize>> = vec![Option::None; size];
+    let mut incoming: Vec<Option<usize>> = vec![Option::None; size];
+    let mut cycles: VecDeque<(usize, usize, Vec<usize>)> = VecDeque::new();
+    let root_idx = index[&root];
+    seen[root_idx] = Option::Some(root_idx);
+    for start in 0..size {
+        let mut vert = start;
+        let mut path: Vec<usize> = Vec::new();
+        let mut chosen: Vec<usize> = Vec::new();
+        while seen[vert].is_none() {
+            let head = heads[vert].unwrap();
+            let (edge, weight) = heap.top(head);
+            heap.nodes[head].delta -= weight;
+            heads[vert] = heap.pop(head);
+            chosen.push(edge);
+            path.push(vert);
+            seen[vert] = Option::Some(start);
+            vert = components.find(index[&edges[edge].from]);
+            if seen[vert] == Option::Some(start) {
+                let mut cycle = Option::None;
+                let end = chosen.len();
+                let time = components.time();
+                loop {
+                    let member = path.pop().unwrap();
+                    cycle = heap.merge(cycle, heads[member]);
+                    if !components.join(vert, member) {
+                        break;
+                    }
+                }
+                let cycle_edges = chosen[path.len()..end].to_vec();
+                chosen.truncate(path.len());
+                vert = components.find(vert);
+                heads[vert] = cycle;
+                seen[vert] = Option::None;
+                cycles.push_front((vert, time, cycle_edges));
+            }
+        }
+        for edge in chosen.iter() {
+            incoming[components.find(index[&edges[*edge].to])] = Option::Some(*edge);
+        }
+    }
+    for (vert, time, cycle_edges) in cycles.iter() {
+        components.rollback(*time);
+        let entering = incoming[*vert];
+        for edge in cycle_edges.iter() {
+            incoming[components.find(index[&edges[*edge].to])] = Option::Some(*edge);
+        }
+        if let Some(edge) = entering {
+            incoming[components.find(index[&edges[edge].to])] = Option::Some(edge);
+        }
+    }
+    let mut result = Edges::new();
+    let mut cost = 0;
+    for (idx, edge) in incoming.iter().enumerate() {
+        if idx == root_idx {
+            continue;
+        }
+        let edge = edges[edge.unwrap()];
+        cost += weight(&edge.value);
+        result.add_edge(edge.clone());
+    }
+    Result::Ok(Arborescence {
+        root,
+        edges: result,
+        cost,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::{directed_weighted, random_multigraph};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn brute_force(graph: &Graph, root: u32) -> Option<u64> {
+        let vertices = graph.vertices();
+        let incoming: Vec<Vec<(u32, u64)>> = vertices
+            .iter()
+            .filter(|vert| **vert != root)
+            .map(|vert| {
+                graph
+                    .adj
+                    .values()
+                    .flat_map(|edges| edges.iter())
+                    .filter(|edge| edge.to == *vert && edge.from != *vert)
+                    .map(|edge| (edge.from, edge.value.unwrap_or(1) as u64))
+                    .collect()
+            })
+            .collect();
+        let targets: Vec<u32> = vertices.iter().copied().filter(|v| *v != root).collect();
+        let mut best = Option::None;
+        let mut choice = vec![0; targets.len()];
+        if incoming.iter().any(|edges| edges.is_empty()) {
+            return Option::None;
+        }
+        loop {
+            let parent: HashMap<u32, u32> = targets
+                .iter()
+                .enumerate()
+                .map(|(idx, vert)| (*vert, incoming[idx][choice[idx]].0))
+                .collect();
+            let is_tree = targets.iter().all(|vert| {
+                let mut curr = *vert;
+                for _ in 0..targets.len() {
+                    if curr == root {
+                        return true;
+                    }
+                    curr = parent[&curr];
+                }
+                curr == root
+            });
+            if is_tree {
+                let cost = (0..targets.len())
+                    .map(|idx| incoming[idx][choice[idx]].1)
+                    .sum::<u64>();
+                best = Option::Some(best.map_or(cost, |b: u64| b.min(cost)));
+            }
+            let mut idx = 0;
+            while idx < choice.len() {
+                choice[idx] += 1;
+                if choice[idx] < incoming[idx].len() {
+                    break;
+                }
+                choice[idx] = 0;
+                idx += 1;
+            }
+            if idx == choice.len() {
+                return best;
+            }
+        }
+    }
+
+    #[test]
+    fn contracts_cycles_between_cheap_edges() {
+        let graph = directed_weighted(&[
+            (0, 1, 10),
+            (0, 2, 10),
+            (1, 2, 1),
+            (2, 1, 1),
+            (2, 3, 4),
+            (1, 3, 8),
+            (3, 3, 0),
+        ]);
+        let tree = minimum_arborescence(&graph, 0).unwrap();
+        assert_eq!(tree.root(), 0);
+        assert_eq!(tree.cost(), 15);
+        let mut edges: Vec<(u32, u32)> = tree
+            .edges()
+            .iter()
+            .map(|edge| (edge.from(), edge.to()))
+            .collect();
+        edges.sort();
+        assert!(edges == vec![(0, 1), (1, 2), (2, 3)] || edges == vec![(0, 2), (2, 1), (2, 3)]);
+    }
+
+    #[test]
+    fn reports_missing_root_or_unreachable_vertex() {
+        let graph = directed_weighted(&[(1, 2, 1), (3, 2, 1)]);
+        assert!(matches!(
+            minimum_arborescence(&graph, 9),
+            Result::Err(ArborescenceError::MissingRoot)
+        ));
+        assert!(matches!(
+            minimum_arborescence(&graph, 1),
+            Result::Err(ArborescenceError::Unreachable(3))
+        ));
+        let mut single: Graph = Graph::new();
+        single.add_vertex(4);
+        let tree = minimum_arborescence(&single, 4).unwrap();
+        assert_eq!(tree.cost(), 0);
+        assert_eq!(tree.edges().len(), 0);
+    }
+
+    #[test]
+    fn rejects_weights_beyond_i64() {
+        let mut graph: Graph<(), u64> = Graph::new();
+        graph.add_edge(1, 2, 5);
+        graph.add_edge(1, 2, u64::MAX);
+        let tree = minimum_arborescence_by(&graph, 1, |value| *value);
+        assert!(matches!(
+            tree,
+            Result::Err(ArborescenceError::WeightTooLarge(u64::MAX))
+        ));
+        let mut bounded: Graph<(), u64> = Graph::new();
+        bounded.add_edge(1, 2, i64::MAX as u64);
+        let tree = minimum_arborescence_by(&bounded, 1, |value| *value).unwrap();
+        assert_eq!(tree.cost(), i64::MAX as u64);
+    }
+
+    #[test]
+    fn matches_brute_force_on_random_graphs() {
+        let mut rng = StdRng::seed_from_u64(43);
+        for _ in 0..200 {
+            let vertices = rng.gen_range(2..6);
+            let edges = rng.gen_range(vertices..vertices * 3) as usize;
+            let graph = random_multigraph(&mut rng, vertices, edges, 0..20, false);
+            let expected = brute_force(&graph, 0);
+            match minimum_arborescence(&graph, 0) {
+                Result::Ok(tree) => {
+                    assert_eq!(Option::Some(tree.cost()), expected);
+                    assert_eq!(tree.edges().len(), vertices as usize - 1);
+                }
+                Result::Err(error) => {
+                    assert_eq!(expected, Option::None);
+                    assert!(matches!(error, ArborescenceError::Unreachable(vert) if vert != 0));
+                }
+            }
+        }
+    }
+}
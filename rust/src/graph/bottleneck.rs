@@ -0,0 +1,279 @@
+use crate::graph::{
+    minimal_spanning_tree_kruskal_by, Distance, Edge, Graph, Path, PositionTrackingMinHeap,
+    VertAttributes,
+};
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+
+fn trace_path<E: Clone>(
+    predecessors: &HashMap<u32, &Edge<E>>,
+    start: u32,
+    end: u32,
+    cost: u32,
+) -> Option<Path<u32, Edge<E>, u32>> {
+    let mut vertices = vec![end];
+    let mut steps = Vec::new();
+    let mut curr = end;
+    while curr != start {
+        let edge = *predecessors.get(&curr)?;
+        steps.push(edge.clone());
+        vertices.push(edge.from);
+        curr = edge.from;
+    }
+    vertices.reverse();
+    steps.reverse();
+    Option::Some(Path::new(vertices, steps, cost))
+}
+
+fn bottleneck_search<N, E>(
+    graph: &Graph<N, E>,
+    start: u32,
+    end: u32,
+    weight: impl Fn(&E) -> u32,
+    key_through: impl Fn(u32, u32) -> u32,
+) -> Option<(u32, HashMap<u32, &Edge<E>>)> {
+    if !graph.contains_vertex(start) || !graph.contains_vertex(end) {
+        return Option::None;
+    }
+    let mut heap: PositionTrackingMinHeap<u32> = PositionTrackingMinHeap::new();
+    for vert in graph.adj.keys() {
+        if *vert != start {
+            heap.insert(VertAttributes::new_inf(*vert));
+        }
+    }
+    heap.insert(VertAttributes::new_zero(start));
+    let mut predecessors: HashMap<u32, &Edge<E>> = HashMap::new();
+    while !heap.is_empty() {
+        let vert_attr = heap.extract_min();
+        let key = match vert_attr.distance {
+            Distance::Inf => return Option::None,
+            Distance::Dist(key) => key,
+        };
+        if vert_attr.id == end {
+            return Option::Some((key, predecessors));
+        }
+        for edge in graph.edges_from(vert_attr.id) {
+            if let Some(idx) = heap.vert_index(edge.to) {
+                let through = Distance::Dist(key_through(key, weight(&edge.value)));
+                if through < heap.get(idx).unwrap().distance {
+                    predecessors.insert(edge.to, edge);
+                    heap.heap_decrease_key(idx, VertAttributes::new_dist(edge.to, through));
+                }
+            }
+        }
+    }
+    Option::None
+}
+
+pub fn widest_path(graph: &Graph, start: u32, end: u32) -> Option<Path<u32, Edge, u32>> {
+    widest_path_by(graph, start, end, |value| value.unwrap_or(1))
+}
+
+pub fn widest_path_by<N, E: Clone>(
+    graph: &Graph<N, E>,
+    start: u32,
+    end: u32,
+    capacity: impl Fn(&E) -> u32,
+) -> Option<Path<u32, Edge<E>, u32>> {
+    let (key, predecessors) = bottleneck_search(graph, start, end, capacity, |key, capacity| {
+        key.max(u32::MAX - capacity)
+    })?;
+    trace_path(&predecessors, start, end, u32::MAX - key)
+}
+
+pub fn minimax_path(graph: &Graph, start: u32, end: u32) -> Option<Path<u32, Edge, u32>> {
+    minimax_path_by(graph, start, end, |value| value.unwrap_or(1))
+}
+
+pub fn minimax_path_by<N, E: Clone>(
+    graph: &Graph<N, E>,
+    start: u32,
+    end: u32,
+    weight: impl Fn(&E) -> u32,
+) -> Option<Path<u32, Edge<E>, u32>> {
+    let (key, predecessors) =
+        bottleneck_search(graph, start, end, weight, |key, weight| key.max(weight))?;
+    trace_path(&predecessors, start, end, key)
+}
+
+pub struct BottleneckQueries {
+    index: HashMap<u32, usize>,
+    component: Vec<usize>,
+    depth: Vec<usize>,
+    up: Vec<Vec<usize>>,
+    bottleneck: Vec<Vec<u32>>,
+    identity: u32,
+    combine: fn(u32, u32) -> u32,
+}
+
+impl BottleneckQueries {
+    pub fn widest(graph: &Graph) -> BottleneckQueries {
+        BottleneckQueries::widest_by(graph, |value| value.unwrap_or(1))
+    }
+
+    pub fn widest_by<N, E: Clone>(
+        graph: &Graph<N, E>,
+        capacity: impl Fn(&E) -> u32,
+    ) -> BottleneckQueries {
+        let forest = minimal_spanning_tree_kruskal_by(graph, |value| Reverse(capacity(value)));
+        BottleneckQueries::new(graph, forest.iter(), capacity, u32::MAX, u32::min)
+    }
+
+    pub fn minimax(graph: &Graph) -> BottleneckQueries {
+        BottleneckQueries::minimax_by(graph, |value| value.unwrap_or(1))
+    }
+
+    pub fn minimax_by<N, E: Clone>(
+        graph: &Graph<N, E>,
+        weight: impl Fn(&E) -> u32,
+    ) -> BottleneckQueries {
+        let forest = minimal_spanning_tree_kruskal_by(graph, &weight);
+        BottleneckQueries::new(graph, forest.iter(), weight, 0, u32::max)
+    }
+
+    fn new<'a, N, E: 'a>(
+        graph: &Graph<N, E>,
+        forest: impl Iterator<Item = &'a Edge<E>>,
+        weight: impl Fn(&E) -> u32,
+        identity: u32,
+        combine: fn(u32, u32) -> u32,
+    ) -> BottleneckQueries {
+        let vertices = graph.vertices();
+        let index: HashMap<u32, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(idx, vert)| (*vert, idx))
+            .collect();
+        let size = vertices.len();
+        let mut tree: Vec<Vec<(usize, u32)>> = vec![Vec::new(); size];
+        for edge in forest {
+            let (from, to) = (index[&edge.from], index[&edge.to]);
+            let value = weight(&edge.value);
+            tree[from].push((to, value));
+            tree[to].push((from, value));
+        }
+        let mut component = vec![usize::MAX; size];
+        let mut depth = vec![0; size];
+        let mut parent: Vec<usize> = (0..size).collect();
+        let mut parent_value = vec![identity; size];
+        for root in 0..size {
+            if component[root] != usize::MAX {
+                continue;
+            }
+            component[root] = root;
+            let mut fifo = VecDeque::new();
+            fifo.push_back(root);
+            while let Some(vert) = fifo.pop_front() {
+                for (next, value) in tree[vert].iter() {
+                    if component[*next] == usize::MAX {
+                        component[*next] = root;
+                        depth[*next] = depth[vert] + 1;
+                        parent[*next] = vert;
+                        parent_value[*next] = *value;
+                        fifo.push_back(*next);
+                    }
+                }
+            }
+        }
+        let mut up = vec![parent];
+        let mut bottleneck = vec![parent_value];
+        while 1 << up.len() < size {
+            let (prev_up, prev_value) = (up.last().unwrap(), bottleneck.last().unwrap());
+            let next_up: Vec<usize> = (0..size).map(|v| prev_up[prev_up[v]]).collect();
+            let next_value: Vec<u32> = (0..size)
+                .map(|v| combine(prev_value[v], prev_value[prev_up[v]]))
+                .collect();
+            up.push(next_up);
+            bottleneck.push(next_value);
+        }
+        BottleneckQueries {
+            index,
+            component,
+            depth,
+            up,
+            bottleneck,
+            identity,
+            combine,
+        }
+    }
+
+    pub fn query(&self, first: u32, second: u32) -> Option<u32> {
+        let mut first = *self.index.get(&first)?;
+        let mut second = *self.index.get(&second)?;
+        if self.component[first] != self.component[second] {
+            return Option::None;
+        }
+        if self.depth[first] < self.depth[second] {
+            std::mem::swap(&mut first, &mut second);
+        }
+        let mut result = self.identity;
+        let diff = self.depth[first] - self.depth[second];
+        for level in 0..self.up.len() {
+            if diff & (1 << level) != 0 {
+                result = (self.combine)(result, self.bottleneck[level][first]);
+                first = self.up[level][first];
+            }
+        }
+        if first == second {
+            return Option::Some(result);
+        }
+        for level in (0..self.up.len()).rev() {
+            if self.up[level][first] != self.up[level][second] {
+                result = (self.combine)(result, self.bottleneck[level][first]);
+                result = (self.combine)(result, self.bottleneck[level][second]);
+                first = self.up[level][first];
+                second = self.up[level][second];
+            }
+        }
+        result = (self.combine)(result, self.bottleneck[0][first]);
+        result = (self.combine)(result, self.bottleneck[0][second]);
+        Option::Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::{directed_weighted, random_multigraph};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn widest_and_minimax_pick_different_routes() {
+        let graph = directed_weighted(&[(1, 2, 5), (2, 4, 6), (1, 3, 2), (3, 4, 3), (5, 1, 9)]);
+        let path = widest_path(&graph, 1, 4).unwrap();
+        assert_eq!(path.vertices(), &vec![1, 2, 4]);
+        assert_eq!(path.cost(), 5);
+        let path = minimax_path(&graph, 1, 4).unwrap();
+        assert_eq!(path.vertices(), &vec![1, 3, 4]);
+        assert_eq!(path.cost(), 3);
+        assert!(widest_path(&graph, 1, 5).is_none());
+        assert!(minimax_path(&graph, 1, 9).is_none());
+        assert_eq!(minimax_path(&graph, 1, 1).unwrap().hops(), 0);
+    }
+
+    #[test]
+    fn queries_match_single_pair_searches() {
+        let mut rng = StdRng::seed_from_u64(45);
+        for _ in 0..50 {
+            let vertices = rng.gen_range(2..12);
+            let edges = rng.gen_range(0..vertices * 2) as usize;
+            let graph = random_multigraph(&mut rng, vertices, edges, 1..50, true);
+            let widest = BottleneckQueries::widest(&graph);
+            let minimax = BottleneckQueries::minimax(&graph);
+            for first in 0..vertices {
+                for second in 0..vertices {
+                    assert_eq!(
+                        widest.query(first, second),
+                        widest_path(&graph, first, second).map(|path| path.cost())
+                    );
+                    assert_eq!(
+                        minimax.query(first, second),
+                        minimax_path(&graph, first, second).map(|path| path.cost())
+                    );
+                }
+            }
+            assert_eq!(widest.query(0, vertices), Option::None);
+        }
+    }
+}
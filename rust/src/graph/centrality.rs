@@ -0,0 +1,418 @@
+use crate::graph::{
+    initialize_single_source_dijkstra, Distance, Graph, PositionTrackingMinHeap, VertAttributes,
+    Weight,
+};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+struct ShortestPathsDag {
+    order: Vec<u32>,
+    predecessors: HashMap<u32, Vec<u32>>,
+    paths_count: HashMap<u32, f64>,
+}
+
+impl ShortestPathsDag {
+    fn new<N, E>(graph: &Graph<N, E>, source: u32) -> ShortestPathsDag {
+        let mut predecessors = HashMap::new();
+        let mut paths_count = HashMap::new();
+        for (vert, _) in graph.adj.iter() {
+            predecessors.insert(*vert, Vec::new());
+            paths_count.insert(*vert, 0.0);
+        }
+        paths_count.insert(source, 1.0);
+        ShortestPathsDag {
+            order: Vec::new(),
+            predecessors,
+            paths_count,
+        }
+    }
+
+    fn add_path(&mut self, from: u32, to: u32) {
+        let through = *self.paths_count.get(&from).unwrap();
+        *self.paths_count.get_mut(&to).unwrap() += through;
+        self.predecessors.get_mut(&to).unwrap().push(from);
+    }
+
+    fn replace_paths(&mut self, from: u32, to: u32) {
+        self.paths_count.insert(to, 0.0);
+        self.predecessors.get_mut(&to).unwrap().clear();
+        self.add_path(from, to);
+    }
+}
+
+fn shortest_paths_bfs<N, E>(graph: &Graph<N, E>, source: u32) -> ShortestPathsDag {
+    let mut result = ShortestPathsDag::new(graph, source);
+    let mut distance: HashMap<u32, u32> = HashMap::new();
+    distance.insert(source, 0);
+    let mut fifo = VecDeque::new();
+    fifo.push_back(source);
+    while let Some(v) = fifo.pop_front() {
+        result.order.push(v);
+        let v_dist = *distance.get(&v).unwrap();
+        for edge in graph.adj.get(&v).unwrap().iter() {
+            match distance.get(&edge.to) {
+                None => {
+                    distance.insert(edge.to, v_dist + 1);
+                    fifo.push_back(edge.to);
+                    result.add_path(v, edge.to);
+                }
+                Some(dist) => {
+                    if *dist == v_dist + 1 {
+                        result.add_path(v, edge.to);
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+fn shortest_paths_dijkstra<N, E, W: Weight>(
+    graph: &Graph<N, E>,
+    source: u32,
+    weight: &impl Fn(&E) -> W,
+) -> ShortestPathsDag {
+    let mut result = ShortestPathsDag::new(graph, source);
+    let mut distance: HashMap<u32, Distance<W>> = HashMap::new();
+    for (vert, _) in graph.adj.iter() {
+        distance.insert(*vert, Distance::Inf);
+    }
+    distance.insert(source, Distance::Dist(W::zero()));
+    let mut heap = initialize_single_source_dijkstra(graph, source);
+    while !heap.is_empty() {
+        let vert_attr = heap.extract_min();
+        if vert_attr.distance == Distance::Inf {
+            break;
+        }
+        result.order.push(vert_attr.id);
+        for edge in graph.adj.get(&vert_attr.id).unwrap().iter() {
+            let dist_through = vert_attr.distance + Distance::Dist(weight(&edge.value));
+            let to_distance = *distance.get(&edge.to).unwrap();
+            if dist_through < to_distance {
+                if let Some(idx) = heap.vert_index(edge.to) {
+                    distance.insert(edge.to, dist_through);
+                    heap.heap_decrease_key(idx, VertAttributes::new_dist(edge.to, dist_through));
+                    result.replace_paths(vert_attr.id, edge.to);
+                }
+            } else if dist_through == to_distance && heap.vert_index(edge.to).is_some() {
+                result.add_path(vert_attr.id, edge.to);
+            }
+        }
+    }
+    result
+}
+
+fn accumulate_dependencies(dag: &ShortestPathsDag, source: u32, scores: &mut BTreeMap<u32, f64>) {
+    let mut dependency: HashMap<u32, f64> = HashMap::new();
+    for vert in dag.order.iter() {
+        dependency.insert(*vert, 0.0);
+    }
+    for w in dag.order.iter().rev() {
+        let w_paths = *dag.paths_count.get(w).unwrap();
+        let w_dependency = *dependency.get(w).unwrap();
+        for v in dag.predecessors.get(w).unwrap().iter() {
+            let v_paths = *dag.paths_count.get(v).unwrap();
+            *dependency.get_mut(v).unwrap() += v_paths / w_paths * (1.0 + w_dependency);
+        }
+        if *w != source {
+            *scores.get_mut(w).unwrap() += w_dependency;
+        }
+    }
+}
+
+fn betweenness<N, E>(
+    graph: &Graph<N, E>,
+    dag_builder: impl Fn(&Graph<N, E>, u32) -> ShortestPathsDag,
+) -> BTreeMap<u32, f64> {
+    let mut scores = BTreeMap::new();
+    for (vert, _) in graph.adj.iter() {
+        scores.insert(*vert, 0.0);
+    }
+    for (source, _) in graph.adj.iter() {
+        let dag = dag_builder(graph, *source);
+        accumulate_dependencies(&dag, *source, &mut scores);
+    }
+    if graph.is_undirected_graph() {
+        for score in scores.values_mut() {
+            *score /= 2.0;
+        }
+    }
+    scores
+}
+
+pub fn betweenness_centrality<N, E>(graph: &Graph<N, E>) -> BTreeMap<u32, f64> {
+    betweenness(graph, shortest_paths_bfs)
+}
+
+pub fn betweenness_centrality_weighted(graph: &Graph) -> BTreeMap<u32, f64> {
+    betweenness_centrality_weighted_by(graph, |value| value.unwrap_or(1))
+}
+
+pub fn betweenness_centrality_weighted_by<N, E, W: Weight>(
+    graph: &Graph<N, E>,
+    weight: impl Fn(&E) -> W,
+) -> BTreeMap<u32, f64> {
+    betweenness(graph, |graph, source| {
+        shortest_paths_dijkstra(graph, source, &weight)
+    })
+}
+
+fn single_source_distances<N, E, W: Weight + Into<u64>>(
+    graph: &Graph<N, E>,
+    source: u32,
+    weight: &impl Fn(&E) -> W,
+) -> HashMap<u32, u64> {
+    let mut result = HashMap::new();
+    let mut heap: PositionTrackingMinHeap<u32, W> =
+        initialize_single_source_dijkstra(graph, source);
+    while !heap.is_empty() {
+        let vert_attr = heap.extract_min();
+        let vert_dist = match vert_attr.distance {
+            Distance::Inf => break,
+            Distance::Dist(d) => d,
+        };
+        result.insert(vert_attr.id, vert_dist.into());
+        for edge in graph.adj.get(&vert_attr.id).unwrap().iter() {
+            if let Some(idx) = heap.vert_index(edge.to) {
+                let dist_through = vert_attr.distance + Distance::Dist(weight(&edge.value));
+                if dist_through < heap.get(idx).unwrap().distance {
+                    heap.heap_decrease_key(idx, VertAttributes::new_dist(edge.to, dist_through));
+                }
+            }
+        }
+    }
+    result
+}
+
+fn single_source_hops<N, E>(graph: &Graph<N, E>, source: u32) -> HashMap<u32, u64> {
+    let mut result = HashMap::new();
+    result.insert(source, 0);
+    let mut fifo = VecDeque::new();
+    fifo.push_back(source);
+    while let Some(v) = fifo.pop_front() {
+        let v_dist = *result.get(&v).unwrap();
+        for edge in graph.adj.get(&v).unwrap().iter() {
+            if let Entry::Vacant(entry) = result.entry(edge.to) {
+                entry.insert(v_dist + 1);
+                fifo.push_back(edge.to);
+            }
+        }
+    }
+    result
+}
+
+fn closeness<N, E>(
+    graph: &Graph<N, E>,
+    distances: impl Fn(&Graph<N, E>, u32) -> HashMap<u32, u64>,
+) -> BTreeMap<u32, f64> {
+    let mut result = BTreeMap::new();
+    let vertices = graph.adj.len();
+    for (vert, _) in graph.adj.iter() {
+        let reachable = distances(graph, *vert);
+        let total: u64 = reachable.values().sum();
+        let mut score = 0.0;
+        if total > 0 && vertices > 1 {
+            let others = (reachable.len() - 1) as f64;
+            score = others / total as f64 * (others / (vertices - 1) as f64);
+        }
+        result.insert(*vert, score);
+    }
+    result
+}
+
+pub fn closeness_centrality<N, E>(graph: &Graph<N, E>) -> BTreeMap<u32, f64> {
+    closeness(graph, single_source_hops)
+}
+
+pub fn closeness_centrality_weighted(graph: &Graph) -> BTreeMap<u32, f64> {
+    closeness_centrality_weighted_by(graph, |value| value.unwrap_or(1))
+}
+
+pub fn closeness_centrality_weighted_by<N, E, W: Weight + Into<u64>>(
+    graph: &Graph<N, E>,
+    weight: impl Fn(&E) -> W,
+) -> BTreeMap<u32, f64> {
+    closeness(graph, |graph, source| {
+        single_source_distances(graph, source, &weight)
+    })
+}
+
+pub fn degree_centrality<N, E>(graph: &Graph<N, E>) -> BTreeMap<u32, f64> {
+    let mut result = BTreeMap::new();
+    let scale = if graph.adj.len() > 1 {
+        1.0 / (graph.adj.len() - 1) as f64
+    } else {
+        0.0
+    };
+    for (vert, edges) in graph.adj.iter() {
+        result.insert(*vert, edges.len() as f64 * scale);
+    }
+    result
+}
+
+pub fn in_degree_centrality<N, E>(graph: &Graph<N, E>) -> BTreeMap<u32, f64> {
+    let mut result = BTreeMap::new();
+    for (vert, _) in graph.adj.iter() {
+        result.insert(*vert, 0.0);
+    }
+    let scale = if graph.adj.len() > 1 {
+        1.0 / (graph.adj.len() - 1) as f64
+    } else {
+        0.0
+    };
+    for (_, edges) in graph.adj.iter() {
+        for edge in edges.iter() {
+            *result.get_mut(&edge.to).unwrap() += scale;
+        }
+    }
+    result
+}
+
+pub fn page_rank<N, E>(
+    graph: &Graph<N, E>,
+    damping: f64,
+    tolerance: f64,
+    max_iterations: usize,
+) -> BTreeMap<u32, f64> {
+    let vertices = graph.adj.len();
+    let mut rank = BTreeMap::new();
+    if vertices == 0 {
+        return rank;
+    }
+    let initial = 1.0 / vertices as f64;
+    for (vert, _) in graph.adj.iter() {
+        rank.insert(*vert, initial);
+    }
+    for _ in 0..max_iterations {
+        let mut dangling_sum = 0.0;
+        for (vert, edges) in graph.adj.iter() {
+            if edges.len() == 0 {
+                dangling_sum += rank.get(vert).unwrap();
+            }
+        }
+        let base = (1.0 - damping) / vertices as f64 + damping * dangling_sum / vertices as f64;
+        let mut next: BTreeMap<u32, f64> = BTreeMap::new();
+        for (vert, _) in graph.adj.iter() {
+            next.insert(*vert, base);
+        }
+        for (vert, edges) in graph.adj.iter() {
+            let share = damping * rank.get(vert).unwrap() / edges.len() as f64;
+            for edge in edges.iter() {
+                *next.get_mut(&edge.to).unwrap() += share;
+            }
+        }
+        let mut change = 0.0;
+        for (vert, _) in graph.adj.iter() {
+            change += (next.get(vert).unwrap() - rank.get(vert).unwrap()).abs();
+        }
+        rank = next;
+        if change < tolerance {
+            break;
+        }
+    }
+    rank
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::undirected_weighted;
+    use crate::graph::Edge;
+
+    fn star(leaves: u32) -> Graph {
+        let edges: Vec<(u32, u32, u32)> = (1..=leaves).map(|leaf| (0, leaf, 1)).collect();
+        undirected_weighted(&edges)
+    }
+
+    fn close(actual: f64, expected: f64) -> bool {
+        (actual - expected).abs() < 1e-9
+    }
+
+    #[test]
+    fn star_centre_dominates_every_measure() {
+        let graph = star(4);
+        let betweenness = betweenness_centrality(&graph);
+        assert_eq!(
+            betweenness.keys().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert!(close(betweenness[&0], 6.0));
+        assert!(close(betweenness[&1], 0.0));
+        let closeness = closeness_centrality(&graph);
+        assert!(close(closeness[&0], 1.0));
+        assert!(close(closeness[&3], 4.0 / 7.0));
+        let degree = degree_centrality(&graph);
+        assert!(close(degree[&0], 1.0));
+        assert!(close(degree[&4], 0.25));
+    }
+
+    #[test]
+    fn weights_reroute_shortest_paths() {
+        let graph = undirected_weighted(&[(1, 2, 1), (2, 3, 1), (1, 3, 5)]);
+        assert!(close(betweenness_centrality(&graph)[&2], 0.0));
+        assert!(close(betweenness_centrality_weighted(&graph)[&2], 1.0));
+        assert!(close(closeness_centrality(&graph)[&1], 1.0));
+        assert!(close(closeness_centrality_weighted(&graph)[&1], 2.0 / 3.0));
+    }
+
+    #[test]
+    fn equal_length_paths_share_betweenness() {
+        let graph = undirected_weighted(&[(1, 2, 1), (1, 3, 1), (2, 4, 1), (3, 4, 1)]);
+        let scores = betweenness_centrality(&graph);
+        assert!(close(scores[&2], 0.5));
+        assert!(close(scores[&3], 0.5));
+        let weighted = betweenness_centrality_weighted(&graph);
+        assert!(close(weighted[&2], 0.5));
+    }
+
+    #[test]
+    fn undirected_pairs_are_counted_once() {
+        let path = undirected_weighted(&[(1, 2, 1), (2, 3, 1)]);
+        let scores = betweenness_centrality(&path);
+        assert!(close(scores[&2], 1.0));
+        assert!(close(scores[&1], 0.0));
+        let mut directed: Graph = Graph::new();
+        directed.add_adj(Edge::new(1, 2));
+        directed.add_adj(Edge::new(2, 3));
+        assert!(close(betweenness_centrality(&directed)[&2], 1.0));
+        directed.add_adj(Edge::new(3, 2));
+        directed.add_adj(Edge::new(2, 1));
+        assert!(close(betweenness_centrality(&directed)[&2], 2.0));
+    }
+
+    #[test]
+    fn in_degree_and_unreachable_closeness_on_a_digraph() {
+        let mut graph: Graph = Graph::new();
+        graph.add_adj(Edge::new(1, 2));
+        graph.add_adj(Edge::new(3, 2));
+        graph.add_vertex(4);
+        let in_degree = in_degree_centrality(&graph);
+        assert!(close(in_degree[&2], 2.0 / 3.0));
+        assert!(close(in_degree[&1], 0.0));
+        let closeness = closeness_centrality(&graph);
+        assert!(close(closeness[&1], 1.0 / 3.0));
+        assert!(close(closeness[&2], 0.0));
+    }
+
+    #[test]
+    fn page_rank_is_a_distribution() {
+        let mut cycle: Graph = Graph::new();
+        for (from, to) in [(1, 2), (2, 3), (3, 1)].iter() {
+            cycle.add_adj(Edge::new(*from, *to));
+        }
+        let ranks = page_rank(&cycle, 0.85, 1e-12, 100);
+        for rank in ranks.values() {
+            assert!(close(*rank, 1.0 / 3.0));
+        }
+        let mut graph: Graph = Graph::new();
+        for (from, to) in [(1, 2), (3, 2), (4, 2), (2, 1)].iter() {
+            graph.add_adj(Edge::new(*from, *to));
+        }
+        graph.add_vertex(5);
+        let ranks = page_rank(&graph, 0.85, 1e-12, 200);
+        assert!(close(ranks.values().sum(), 1.0));
+        assert!(ranks[&2] > ranks[&1] && ranks[&1] > ranks[&3]);
+        assert!(close(ranks[&3], ranks[&4]));
+        assert!(page_rank(&Graph::<(), Option<u32>>::new(), 0.85, 1e-9, 10).is_empty());
+    }
+}
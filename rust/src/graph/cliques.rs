@@ -0,0 +1,313 @@
+use crate::graph::Graph;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+pub fn degeneracy_ordering<N, E>(graph: &Graph<N, E>) -> Vec<u32> {
+    let neighbours = graph.neighbour_sets();
+    let mut degrees: HashMap<u32, usize> = HashMap::new();
+    let mut queue: BTreeSet<(usize, u32)> = BTreeSet::new();
+    for (vert, adjacent) in neighbours.iter() {
+        degrees.insert(*vert, adjacent.len());
+        queue.insert((adjacent.len(), *vert));
+    }
+    let mut result = Vec::with_capacity(neighbours.len());
+    let mut removed: BTreeSet<u32> = BTreeSet::new();
+    while let Some((_, vert)) = queue.pop_first() {
+        removed.insert(vert);
+        result.push(vert);
+        for neighbour in neighbours.get(&vert).unwrap().iter() {
+            if removed.contains(neighbour) {
+                continue;
+            }
+            let degree = degrees.get_mut(neighbour).unwrap();
+            queue.remove(&(*degree, *neighbour));
+            *degree -= 1;
+            queue.insert((*degree, *neighbour));
+        }
+    }
+    result
+}
+
+struct CliqueFrame {
+    clique: Vec<u32>,
+    candidates: BTreeSet<u32>,
+    excluded: BTreeSet<u32>,
+    branches: Vec<u32>,
+    position: usize,
+}
+
+impl CliqueFrame {
+    fn new(
+        clique: Vec<u32>,
+        candidates: BTreeSet<u32>,
+        excluded: BTreeSet<u32>,
+        neighbours: &BTreeMap<u32, BTreeSet<u32>>,
+    ) -> CliqueFrame {
+        let pivot = candidates
+            .iter()
+            .chain(excluded.iter())
+            .max_by_key(|v| {
+                let adjacent = neighbours.get(v).unwrap();
+                (
+                    candidates.intersection(adjacent).count(),
+                    std::cmp::Reverse(**v),
+                )
+            })
+            .copied();
+        let mut branches: Vec<u32> = match pivot {
+            None => candidates.iter().copied().collect(),
+            Some(pivot) => candidates
+                .difference(neighbours.get(&pivot).unwrap())
+                .copied()
+                .collect(),
+        };
+        branches.sort_unstable();
+        CliqueFrame {
+            clique,
+            candidates,
+            excluded,
+            branches,
+            position: 0,
+        }
+    }
+}
+
+pub struct MaximalCliques {
+    neighbours: BTreeMap<u32, BTreeSet<u32>>,
+    order: Vec<u32>,
+    rank: HashMap<u32, usize>,
+    next_root: usize,
+    stack: Vec<CliqueFrame>,
+}
+
+impl MaximalCliques {
+    pub fn new<N, E>(graph: &Graph<N, E>) -> MaximalCliques {
+        let order = degeneracy_ordering(graph);
+        let mut rank = HashMap::new();
+        for (position, vert) in order.iter().enumerate() {
+            rank.insert(*vert, position);
+        }
+        MaximalCliques {
+            neighbours: graph.neighbour_sets(),
+            order,
+            rank,
+            next_root: 0,
+            stack: Vec::new(),
+        }
+    }
+
+    fn descend(
+        &mut self,
+        clique: Vec<u32>,
+        candidates: BTreeSet<u32>,
+        excluded: BTreeSet<u32>,
+    ) -> Option<Vec<u32>> {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                let mut result = clique;
+                result.sort_unstable();
+                return Option::Some(result);
+            }
+            return Option::None;
+        }
+        let frame = CliqueFrame::new(clique, candidates, excluded, &self.neighbours);
+        self.stack.push(frame);
+        Option::None
+    }
+}
+
+impl Iterator for MaximalCliques {
+    type Item = Vec<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(frame) = self.stack.last_mut() {
+                if frame.position == frame.branches.len() {
+                    self.stack.pop();
+                    continue;
+                }
+                let vert = frame.branches[frame.position];
+                frame.position += 1;
+                let adjacent = self.neighbours.get(&vert).unwrap();
+                let mut clique = frame.clique.clone();
+                clique.push(vert);
+                let candidates = frame.candidates.intersection(adjacent).copied().collect();
+                let excluded = frame.excluded.intersection(adjacent).copied().collect();
+                frame.candidates.remove(&vert);
+                frame.excluded.insert(vert);
+                if let Some(found) = self.descend(clique, candidates, excluded) {
+                    return Option::Some(found);
+                }
+            } else {
+                if self.next_root == self.order.len() {
+                    return Option::None;
+                }
+                let vert = self.order[self.next_root];
+                let mut candidates = BTreeSet::new();
+                let mut excluded = BTreeSet::new();
+                for other in self.neighbours.get(&vert).unwrap().iter() {
+                    if *self.rank.get(other).unwrap() > self.next_root {
+                        candidates.insert(*other);
+                    } else {
+                        excluded.insert(*other);
+                    }
+                }
+                self.next_root += 1;
+                if let Some(found) = self.descend(vec![vert], candidates, excluded) {
+                    return Option::Some(found);
+                }
+            }
+        }
+    }
+}
+
+pub fn maximal_cliques<N, E>(graph: &Graph<N, E>) -> MaximalCliques {
+    MaximalCliques::new(graph)
+}
+
+fn expand_maximum(
+    neighbours: &BTreeMap<u32, BTreeSet<u32>>,
+    clique: &mut Vec<u32>,
+    candidates: BTreeSet<u32>,
+    best: &mut Vec<u32>,
+) {
+    if candidates.is_empty() {
+        if clique.len() > best.len() {
+            *best = clique.clone();
+        }
+        return;
+    }
+    let mut remaining: Vec<u32> = candidates.iter().copied().collect();
+    remaining.sort_unstable();
+    remaining.sort_by_key(|v| {
+        let adjacent = neighbours.get(v).unwrap();
+        std::cmp::Reverse(candidates.intersection(adjacent).count())
+    });
+    let mut candidates = candidates;
+    for vert in remaining {
+        if clique.len() + candidates.len() <= best.len() {
+            return;
+        }
+        let adjacent = neighbours.get(&vert).unwrap();
+        let next = candidates.intersection(adjacent).copied().collect();
+        clique.push(vert);
+        expand_maximum(neighbours, clique, next, best);
+        clique.pop();
+        candidates.remove(&vert);
+    }
+}
+
+pub fn maximum_clique<N, E>(graph: &Graph<N, E>) -> Vec<u32> {
+    let neighbours = graph.neighbour_sets();
+    let order = degeneracy_ordering(graph);
+    let mut best: Vec<u32> = Vec::new();
+    for (position, vert) in order.iter().enumerate() {
+        let adjacent = neighbours.get(vert).unwrap();
+        let candidates: BTreeSet<u32> = order[position + 1..]
+            .iter()
+            .filter(|other| adjacent.contains(other))
+            .copied()
+            .collect();
+        if candidates.len() < best.len() {
+            continue;
+        }
+        let mut clique = vec![*vert];
+        expand_maximum(&neighbours, &mut clique, candidates, &mut best);
+    }
+    best.sort_unstable();
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::random_simple_graph;
+    use crate::graph::Edge;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn is_clique(neighbours: &BTreeMap<u32, BTreeSet<u32>>, members: &[u32]) -> bool {
+        members.iter().enumerate().all(|(idx, first)| {
+            members[idx + 1..]
+                .iter()
+                .all(|second| neighbours[first].contains(second))
+        })
+    }
+
+    fn brute_force_maximal(graph: &Graph) -> Vec<Vec<u32>> {
+        let neighbours = graph.neighbour_sets();
+        let vertices = graph.vertices();
+        let mut result = Vec::new();
+        for mask in 1u32..1 << vertices.len() {
+            let members: Vec<u32> = (0..vertices.len())
+                .filter(|idx| mask & (1 << idx) != 0)
+                .map(|idx| vertices[idx])
+                .collect();
+            if !is_clique(&neighbours, &members) {
+                continue;
+            }
+            let extendable = vertices
+                .iter()
+                .any(|v| !members.contains(v) && members.iter().all(|m| neighbours[v].contains(m)));
+            if !extendable {
+                result.push(members);
+            }
+        }
+        result.sort();
+        result
+    }
+
+    #[test]
+    fn maximal_cliques_match_brute_force() {
+        let mut rng = StdRng::seed_from_u64(32);
+        for _ in 0..60 {
+            let probability = rng.gen_range(0.1..0.9);
+            let graph = random_simple_graph(&mut rng, 9, probability);
+            let mut cliques: Vec<Vec<u32>> = maximal_cliques(&graph).collect();
+            cliques.sort();
+            let expected = brute_force_maximal(&graph);
+            assert_eq!(cliques, expected);
+            let largest = expected.iter().map(|c| c.len()).max().unwrap();
+            let maximum = maximum_clique(&graph);
+            assert_eq!(maximum.len(), largest);
+            assert!(is_clique(&graph.neighbour_sets(), &maximum));
+        }
+    }
+
+    #[test]
+    fn triangle_free_graphs_have_edge_cliques() {
+        let mut petersen: Graph = Graph::new();
+        for i in 0..5 {
+            petersen.add_adj_both(Edge::new(i, (i + 1) % 5));
+            petersen.add_adj_both(Edge::new(i, i + 5));
+            petersen.add_adj_both(Edge::new(i + 5, (i + 2) % 5 + 5));
+        }
+        let cliques: Vec<Vec<u32>> = maximal_cliques(&petersen).collect();
+        assert_eq!(cliques.len(), 15);
+        assert!(cliques.iter().all(|clique| clique.len() == 2));
+        assert_eq!(maximum_clique(&petersen).len(), 2);
+    }
+
+    #[test]
+    fn degeneracy_ordering_peels_low_degree_vertices_first() {
+        let mut graph: Graph = Graph::new();
+        for first in 0..4 {
+            for second in first + 1..4 {
+                graph.add_adj_both(Edge::new(first, second));
+            }
+        }
+        graph.add_adj_both(Edge::new(3, 4));
+        graph.add_adj(Edge::new(4, 4));
+        graph.add_vertex(5);
+        let order = degeneracy_ordering(&graph);
+        assert_eq!(order.len(), 6);
+        assert_eq!(order[0], 5);
+        assert_eq!(order[1], 4);
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, vec![0, 1, 2, 3, 4, 5]);
+        let cliques: Vec<Vec<u32>> = maximal_cliques(&graph).collect();
+        assert!(cliques.contains(&vec![0, 1, 2, 3]));
+        assert!(cliques.contains(&vec![3, 4]));
+        assert!(cliques.contains(&vec![5]));
+    }
+}
@@ -0,0 +1,336 @@
+use crate::graph::Graph;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::{BTreeMap, HashMap};
+
+pub struct Communities {
+    assignment: BTreeMap<u32, u32>,
+    modularity: f64,
+}
+
+impl Communities {
+    fn new<N, E>(
+        graph: &Graph<N, E>,
+        labels: BTreeMap<u32, u32>,
+        weight: &impl Fn(&E) -> f64,
+    ) -> Communities {
+        let mut renumbered: HashMap<u32, u32> = HashMap::new();
+        let mut assignment = BTreeMap::new();
+        for (vert, label) in labels.iter() {
+            let next_id = renumbered.len() as u32;
+            let id = *renumbered.entry(*label).or_insert(next_id);
+            assignment.insert(*vert, id);
+        }
+        let modularity = modularity_by(graph, &assignment, weight);
+        Communities {
+            assignment,
+            modularity,
+        }
+    }
+
+    pub fn community_of(&self, vert: u32) -> Option<u32> {
+        self.assignment.get(&vert).copied()
+    }
+
+    pub fn assignment(&self) -> &BTreeMap<u32, u32> {
+        &self.assignment
+    }
+
+    pub fn modularity(&self) -> f64 {
+        self.modularity
+    }
+
+    pub fn count(&self) -> usize {
+        let mut ids: Vec<u32> = self.assignment.values().copied().collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids.len()
+    }
+
+    pub fn members(&self) -> Vec<Vec<u32>> {
+        let mut result: Vec<Vec<u32>> = vec![Vec::new(); self.count()];
+        for (vert, id) in self.assignment.iter() {
+            result[*id as usize].push(*vert);
+        }
+        for members in result.iter_mut() {
+            members.sort_unstable();
+        }
+        result
+    }
+}
+
+pub fn modularity(graph: &Graph, assignment: &BTreeMap<u32, u32>) -> f64 {
+    modularity_by(graph, assignment, |value| value.unwrap_or(1) as f64)
+}
+
+pub fn modularity_by<N, E>(
+    graph: &Graph<N, E>,
+    assignment: &BTreeMap<u32, u32>,
+    weight: impl Fn(&E) -> f64,
+) -> f64 {
+    let mut total_weight = 0.0;
+    let mut inside: BTreeMap<u32, f64> = BTreeMap::new();
+    let mut degrees: BTreeMap<u32, f64> = BTreeMap::new();
+    for (vert, edges) in graph.adj.iter() {
+        let community = *assignment.get(vert).unwrap();
+        for edge in edges.iter() {
+            let value = weight(&edge.value);
+            total_weight += value;
+            *degrees.entry(community).or_insert(0.0) += value;
+            if *assignment.get(&edge.to).unwrap() == community {
+                *inside.entry(community).or_insert(0.0) += value;
+            }
+        }
+    }
+    let mut result = 0.0;
+    if total_weight > 0.0 {
+        for (community, degree) in degrees.iter() {
+            let internal = *inside.get(community).unwrap_or(&0.0);
+            result += internal / total_weight - (degree / total_weight).powi(2);
+        }
+    }
+    result
+}
+
+pub fn label_propagation(graph: &Graph, seed: u64, max_iterations: usize) -> Communities {
+    label_propagation_by(graph, seed, max_iterations, |value| {
+        value.unwrap_or(1) as f64
+    })
+}
+
+pub fn label_propagation_by<N, E>(
+    graph: &Graph<N, E>,
+    seed: u64,
+    max_iterations: usize,
+    weight: impl Fn(&E) -> f64,
+) -> Communities {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut order = graph.vertices();
+    let mut labels: BTreeMap<u32, u32> = BTreeMap::new();
+    for vert in order.iter() {
+        labels.insert(*vert, *vert);
+    }
+    for _ in 0..max_iterations {
+        order.shuffle(&mut rng);
+        let mut changed = false;
+        for vert in order.iter() {
+            let mut weights: HashMap<u32, f64> = HashMap::new();
+            for edge in graph.adj.get(vert).unwrap().iter() {
+                if edge.to != *vert {
+                    let label = *labels.get(&edge.to).unwrap();
+                    *weights.entry(label).or_insert(0.0) += weight(&edge.value);
+                }
+            }
+            let best_weight = weights.values().copied().fold(0.0, f64::max);
+            let mut candidates: Vec<u32> = weights
+                .iter()
+                .filter(|(_, weight)| **weight == best_weight)
+                .map(|(label, _)| *label)
+                .collect();
+            if candidates.is_empty() {
+                continue;
+            }
+            let current = *labels.get(vert).unwrap();
+            if candidates.contains(&current) {
+                continue;
+            }
+            candidates.sort_unstable();
+            labels.insert(*vert, *candidates.choose(&mut rng).unwrap());
+            changed = true;
+        }
+        if !changed {
+            break;
+        }
+    }
+    Communities::new(graph, labels, &weight)
+}
+
+struct WeightedNetwork {
+    adj: Vec<BTreeMap<usize, f64>>,
+    degrees: Vec<f64>,
+    total_weight: f64,
+}
+
+impl WeightedNetwork {
+    fn from_graph<N, E>(
+        graph: &Graph<N, E>,
+        vertices: &[u32],
+        weight: &impl Fn(&E) -> f64,
+    ) -> WeightedNetwork {
+        let mut index = HashMap::new();
+        for (idx, vert) in vertices.iter().enumerate() {
+            index.insert(*vert, idx);
+        }
+        let mut adj = vec![BTreeMap::new(); vertices.len()];
+        for (idx, vert) in vertices.iter().enumerate() {
+            for edge in graph.adj.get(vert).unwrap().iter() {
+                let to = *index.get(&edge.to).unwrap();
+                *adj[idx].entry(to).or_insert(0.0) += weight(&edge.value);
+            }
+        }
+        WeightedNetwork::from_adj(adj)
+    }
+
+    fn from_adj(adj: Vec<BTreeMap<usize, f64>>) -> WeightedNetwork {
+        let degrees: Vec<f64> = adj.iter().map(|n| n.values().sum()).collect();
+        let total_weight = degrees.iter().sum();
+        WeightedNetwork {
+            adj,
+            degrees,
+            total_weight,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    fn move_nodes(&self, community: &mut [usize]) -> bool {
+        let mut totals = self.degrees.clone();
+        let mut improved = false;
+        let mut moved = true;
+        while moved {
+            moved = false;
+            for node in 0..self.len() {
+                let degree = self.degrees[node];
+                let current = community[node];
+                let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+                links.insert(current, 0.0);
+                for (neighbor, weight) in self.adj[node].iter() {
+                    if *neighbor != node {
+                        *links.entry(community[*neighbor]).or_insert(0.0) += weight;
+                    }
+                }
+                totals[current] -= degree;
+                let gain = |c: usize| links[&c] - totals[c] * degree / self.total_weight;
+                let mut best = current;
+                let mut best_gain = gain(current);
+                for candidate in links.keys().copied() {
+                    let candidate_gain = gain(candidate);
+                    if candidate_gain > best_gain + 1e-12 {
+                        best = candidate;
+                        best_gain = candidate_gain;
+                    }
+                }
+                totals[best] += degree;
+                if best != current {
+                    community[node] = best;
+                    moved = true;
+                    improved = true;
+                }
+            }
+        }
+        improved
+    }
+
+    fn aggregate(&self, community: &mut [usize]) -> WeightedNetwork {
+        let mut renumbered: HashMap<usize, usize> = HashMap::new();
+        for c in community.iter_mut() {
+            let next_id = renumbered.len();
+            *c = *renumbered.entry(*c).or_insert(next_id);
+        }
+        let mut adj = vec![BTreeMap::new(); renumbered.len()];
+        for node in 0..self.len() {
+            for (neighbor, weight) in self.adj[node].iter() {
+                *adj[community[node]]
+                    .entry(community[*neighbor])
+                    .or_insert(0.0) += weight;
+            }
+        }
+        WeightedNetwork::from_adj(adj)
+    }
+}
+
+pub fn louvain(graph: &Graph) -> Communities {
+    louvain_by(graph, |value| value.unwrap_or(1) as f64)
+}
+
+pub fn louvain_by<N, E>(graph: &Graph<N, E>, weight: impl Fn(&E) -> f64) -> Communities {
+    let vertices = graph.vertices();
+    let mut network = WeightedNetwork::from_graph(graph, &vertices, &weight);
+    let mut membership: Vec<usize> = (0..vertices.len()).collect();
+    if network.total_weight > 0.0 {
+        loop {
+            let mut community: Vec<usize> = (0..network.len()).collect();
+            if !network.move_nodes(&mut community) {
+                break;
+            }
+            let aggregated = network.aggregate(&mut community);
+            for m in membership.iter_mut() {
+                *m = community[*m];
+            }
+            network = aggregated;
+        }
+    }
+    let mut labels = BTreeMap::new();
+    for (idx, vert) in vertices.iter().enumerate() {
+        labels.insert(*vert, membership[idx] as u32);
+    }
+    Communities::new(graph, labels, &weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Edge;
+
+    fn two_cliques() -> Graph {
+        let mut graph = Graph::new();
+        for offset in [0, 4].iter() {
+            for first in 0..4 {
+                for second in first + 1..4 {
+                    graph.add_adj_both(Edge::new(offset + first, offset + second));
+                }
+            }
+        }
+        graph.add_adj_both(Edge::new(3, 4));
+        graph
+    }
+
+    #[test]
+    fn modularity_of_known_partitions() {
+        let graph = two_cliques();
+        let split: BTreeMap<u32, u32> = (0..8).map(|v| (v, v / 4)).collect();
+        let expected = 2.0 * (12.0 / 26.0 - (13.0f64 / 26.0).powi(2));
+        assert!((modularity(&graph, &split) - expected).abs() < 1e-12);
+        let together: BTreeMap<u32, u32> = (0..8).map(|v| (v, 0)).collect();
+        assert!(modularity(&graph, &together).abs() < 1e-12);
+    }
+
+    #[test]
+    fn louvain_separates_the_cliques() {
+        let communities = louvain(&two_cliques());
+        assert_eq!(communities.count(), 2);
+        assert_eq!(
+            communities.members(),
+            vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]]
+        );
+        assert_eq!(communities.community_of(0), Option::Some(0));
+        assert_eq!(communities.community_of(9), Option::None);
+        assert!(communities.modularity() > 0.4);
+    }
+
+    #[test]
+    fn label_propagation_is_reproducible_for_a_seed() {
+        let graph = two_cliques();
+        let first = label_propagation(&graph, 11, 50);
+        let second = label_propagation(&graph, 11, 50);
+        assert_eq!(first.assignment(), second.assignment());
+        assert_eq!(first.assignment().len(), 8);
+        for members in first.members() {
+            assert!(members.iter().all(|v| *v < 4) || members.iter().all(|v| *v >= 4));
+        }
+    }
+
+    #[test]
+    fn edgeless_graphs_keep_singletons() {
+        let mut graph: Graph = Graph::new();
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+        let communities = louvain(&graph);
+        assert_eq!(communities.count(), 2);
+        assert_eq!(communities.modularity(), 0.0);
+        assert_eq!(label_propagation(&graph, 3, 10).count(), 2);
+    }
+}
@@ -0,0 +1,213 @@
+use crate::graph::{Edge, Graph, Path};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+
+type HopEntry<'a, E> = (u64, Option<(&'a Edge<E>, usize)>);
+
+pub fn shortest_path_hop_limited(
+    graph: &Graph,
+    start: u32,
+    end: u32,
+    max_hops: usize,
+) -> Option<Path<u32, Edge, u64>> {
+    shortest_path_hop_limited_by(graph, start, end, max_hops, |value| {
+        value.unwrap_or(1) as u64
+    })
+}
+
+pub fn shortest_path_hop_limited_by<N, E: Clone>(
+    graph: &Graph<N, E>,
+    start: u32,
+    end: u32,
+    max_hops: usize,
+    weight: impl Fn(&E) -> u64,
+) -> Option<Path<u32, Edge<E>, u64>> {
+    if !graph.contains_vertex(start) {
+        return Option::None;
+    }
+    let mut layers: Vec<BTreeMap<u32, HopEntry<E>>> = Vec::new();
+    let mut first = BTreeMap::new();
+    first.insert(start, (0, Option::None));
+    layers.push(first);
+    for hops in 1..=max_hops {
+        let prev = &layers[hops - 1];
+        let mut next = prev.clone();
+        let mut changed = false;
+        for (vert, (dist, _)) in prev.iter() {
+            for edge in graph.edges_from(*vert) {
+                let dist_through = dist + weight(&edge.value);
+                if next.get(&edge.to).is_none_or(|(d, _)| dist_through < *d) {
+                    next.insert(edge.to, (dist_through, Option::Some((edge, hops - 1))));
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+        layers.push(next);
+    }
+    let mut layer = layers.len() - 1;
+    let (cost, _) = *layers[layer].get(&end)?;
+    let mut vertices = vec![end];
+    let mut steps = Vec::new();
+    let mut curr = end;
+    while let (_, Some((edge, prev_layer))) = layers[layer][&curr] {
+        steps.push(edge.clone());
+        vertices.push(edge.from);
+        curr = edge.from;
+        layer = prev_layer;
+    }
+    vertices.reverse();
+    steps.reverse();
+    Option::Some(Path::new(vertices, steps, cost))
+}
+
+struct Label<'a, E> {
+    vert: u32,
+    parent: Option<(usize, &'a Edge<E>)>,
+}
+
+pub fn shortest_path_resource_constrained(
+    graph: &Graph,
+    start: u32,
+    end: u32,
+    resource: impl Fn(&Edge) -> u64,
+    budget: u64,
+) -> Option<Path<u32, Edge, u64>> {
+    shortest_path_resource_constrained_by(
+        graph,
+        start,
+        end,
+        |value| value.unwrap_or(1) as u64,
+        resource,
+        budget,
+    )
+}
+
+pub fn shortest_path_resource_constrained_by<N, E: Clone>(
+    graph: &Graph<N, E>,
+    start: u32,
+    end: u32,
+    weight: impl Fn(&E) -> u64,
+    resource: impl Fn(&Edge<E>) -> u64,
+    budget: u64,
+) -> Option<Path<u32, Edge<E>, u64>> {
+    if !graph.contains_vertex(start) {
+        return Option::None;
+    }
+    let mut labels: Vec<Label<E>> = vec![Label {
+        vert: start,
+        parent: Option::None,
+    }];
+    let mut settled: HashMap<u32, Vec<u64>> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((0, 0, 0)));
+    while let Some(Reverse((cost, used, idx))) = queue.pop() {
+        let vert = labels[idx].vert;
+        let resources = settled.entry(vert).or_default();
+        if resources.iter().any(|r| *r <= used) {
+            continue;
+        }
+        resources.push(used);
+        if vert == end {
+            let mut vertices = vec![end];
+            let mut steps = Vec::new();
+            let mut curr = idx;
+            while let Some((parent, edge)) = labels[curr].parent {
+                steps.push(edge.clone());
+                vertices.push(edge.from);
+                curr = parent;
+            }
+            vertices.reverse();
+            steps.reverse();
+            return Option::Some(Path::new(vertices, steps, cost));
+        }
+        for edge in graph.edges_from(vert) {
+            let next_used = used + resource(edge);
+            if next_used > budget {
+                continue;
+            }
+            let dominated = settled
+                .get(&edge.to)
+                .is_some_and(|resources| resources.iter().any(|r| *r <= next_used));
+            if dominated {
+                continue;
+            }
+            let next_cost = cost + weight(&edge.value);
+            labels.push(Label {
+                vert: edge.to,
+                parent: Option::Some((idx, edge)),
+            });
+            queue.push(Reverse((next_cost, next_used, labels.len() - 1)));
+        }
+    }
+    Option::None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::directed_weighted;
+    use crate::graph::shortest_path_dijkstra;
+
+    #[test]
+    fn hop_limit_trades_cost_for_fewer_edges() {
+        let graph = directed_weighted(&[(1, 2, 1), (2, 3, 1), (3, 4, 1), (1, 4, 10), (2, 4, 5)]);
+        let path = shortest_path_hop_limited(&graph, 1, 4, 3).unwrap();
+        assert_eq!(path.vertices(), &vec![1, 2, 3, 4]);
+        assert_eq!(path.cost(), 3);
+        let path = shortest_path_hop_limited(&graph, 1, 4, 2).unwrap();
+        assert_eq!(path.vertices(), &vec![1, 2, 4]);
+        assert_eq!(path.cost(), 6);
+        let path = shortest_path_hop_limited(&graph, 1, 4, 1).unwrap();
+        assert_eq!(path.vertices(), &vec![1, 4]);
+        assert_eq!(path.edges()[0].value(), &Option::Some(10));
+        assert!(shortest_path_hop_limited(&graph, 1, 4, 0).is_none());
+        assert_eq!(
+            shortest_path_hop_limited(&graph, 1, 1, 0).unwrap().hops(),
+            0
+        );
+        assert!(shortest_path_hop_limited(&graph, 9, 1, 3).is_none());
+    }
+
+    #[test]
+    fn generous_hop_limit_matches_dijkstra() {
+        let graph = directed_weighted(&[
+            (1, 2, 4),
+            (1, 3, 1),
+            (3, 2, 1),
+            (2, 4, 1),
+            (3, 4, 7),
+            (4, 5, 2),
+            (5, 1, 1),
+        ]);
+        for end in 1..=5 {
+            let limited = shortest_path_hop_limited(&graph, 1, end, 10).unwrap();
+            let exact = shortest_path_dijkstra(&graph, 1, end).unwrap();
+            assert_eq!(limited.cost(), exact.cost() as u64);
+            assert_eq!(limited.vertices(), exact.vertices());
+        }
+    }
+
+    #[test]
+    fn resource_budget_keeps_costlier_but_lighter_labels() {
+        let graph = directed_weighted(&[(1, 2, 1), (2, 4, 1), (1, 3, 3), (3, 4, 3)]);
+        let resource = |edge: &Edge| {
+            if edge.from() == 2 || edge.to() == 2 {
+                5
+            } else {
+                1
+            }
+        };
+        let path = shortest_path_resource_constrained(&graph, 1, 4, resource, 10).unwrap();
+        assert_eq!(path.vertices(), &vec![1, 2, 4]);
+        assert_eq!(path.cost(), 2);
+        let path = shortest_path_resource_constrained(&graph, 1, 4, resource, 4).unwrap();
+        assert_eq!(path.vertices(), &vec![1, 3, 4]);
+        assert_eq!(path.cost(), 6);
+        assert_eq!(path.hops(), 2);
+        assert!(shortest_path_resource_constrained(&graph, 1, 4, resource, 1).is_none());
+        assert!(shortest_path_resource_constrained(&graph, 4, 1, resource, 10).is_none());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::graph::{Edge, Graph};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+pub struct NegativeCycle {
+    constraints: Vec<Edge<i64>>,
+}
+
+impl NegativeCycle {
+    pub fn variables(&self) -> Vec<u32> {
+        self.constraints.iter().map(|edge| edge.from).collect()
+    }
+
+    pub fn constraints(&self) -> &Vec<Edge<i64>> {
+        &self.constraints
+    }
+
+    pub fn weight(&self) -> i64 {
+        self.constraints.iter().map(|edge| edge.value).sum()
+    }
+}
+
+pub struct DifferenceConstraints {
+    graph: Graph<(), i64>,
+}
+
+impl DifferenceConstraints {
+    pub fn new() -> DifferenceConstraints {
+        DifferenceConstraints {
+            graph: Graph::new(),
+        }
+    }
+
+    pub fn add_variable(&mut self, var: u32) {
+        self.graph.add_vertex(var);
+    }
+
+    pub fn add_constraint(&mut self, subtrahend: u32, minuend: u32, bound: i64) {
+        self.graph.add_edge(subtrahend, minuend, bound);
+    }
+
+    pub fn variables(&self) -> Vec<u32> {
+        self.graph.vertices()
+    }
+
+    pub fn graph(&self) -> &Graph<(), i64> {
+        &self.graph
+    }
+
+    pub fn solve(&self) -> Result<BTreeMap<u32, i64>, NegativeCycle> {
+        let mut distance: BTreeMap<u32, i64> = BTreeMap::new();
+        for vert in self.graph.adj.keys() {
+            distance.insert(*vert, 0);
+        }
+        let mut predecessors: HashMap<u32, &Edge<i64>> = HashMap::new();
+        let mut last_relaxed = Option::None;
+        for _ in 0..self.graph.adj.len() {
+            last_relaxed = Option::None;
+            for edges in self.graph.adj.values() {
+                for edge in edges.iter() {
+                    let dist_through = distance[&edge.from] + edge.value;
+                    if dist_through < distance[&edge.to] {
+                        distance.insert(edge.to, dist_through);
+                        predecessors.insert(edge.to, edge);
+                        last_relaxed = Option::Some(edge.to);
+                    }
+                }
+            }
+            if last_relaxed.is_none() {
+                break;
+            }
+        }
+        match last_relaxed {
+            None => Result::Ok(distance),
+            Some(vert) => Result::Err(DifferenceConstraints::extract_cycle(&predecessors, vert)),
+        }
+    }
+
+    fn extract_cycle(predecessors: &HashMap<u32, &Edge<i64>>, start: u32) -> NegativeCycle {
+        let mut seen = HashSet::new();
+        let mut curr = start;
+        while seen.insert(curr) {
+            curr = predecessors[&curr].from;
+        }
+        let mut constraints = Vec::new();
+        let cycle_start = curr;
+        loop {
+            let edge = predecessors[&curr];
+            constraints.push(edge.clone());
+            curr = edge.from;
+            if curr == cycle_start {
+                break;
+            }
+        }
+        constraints.reverse();
+        NegativeCycle { constraints }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feasible_system_satisfies_every_constraint() {
+        let constraints = [(1, 2, 5), (2, 3, -2), (1, 3, 1), (3, 1, 4), (2, 4, 0)];
+        let mut system = DifferenceConstraints::new();
+        system.add_variable(5);
+        for (subtrahend, minuend, bound) in constraints.iter() {
+            system.add_constraint(*subtrahend, *minuend, *bound);
+        }
+        let solution = match system.solve() {
+            Ok(solution) => solution,
+            Err(_) => panic!("System should be feasible."),
+        };
+        assert_eq!(
+            solution.keys().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        for (subtrahend, minuend, bound) in constraints.iter() {
+            assert!(solution[minuend] - solution[subtrahend] <= *bound);
+        }
+        assert_eq!(solution[&5], 0);
+    }
+
+    #[test]
+    fn infeasible_system_reports_a_negative_cycle() {
+        let mut system = DifferenceConstraints::new();
+        system.add_constraint(1, 2, 3);
+        system.add_constraint(2, 3, -4);
+        system.add_constraint(3, 1, 0);
+        system.add_constraint(3, 4, 10);
+        let cycle = match system.solve() {
+            Ok(_) => panic!("System should be infeasible."),
+            Err(cycle) => cycle,
+        };
+        assert_eq!(cycle.weight(), -1);
+        let mut variables = cycle.variables();
+        variables.sort_unstable();
+        assert_eq!(variables, vec![1, 2, 3]);
+        for (edge, next) in cycle
+            .constraints()
+            .iter()
+            .zip(cycle.constraints().iter().cycle().skip(1))
+        {
+            assert_eq!(edge.to(), next.from());
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use crate::graph::{Edge, Graph, Path};
+use std::collections::{BTreeSet, HashMap};
+
+pub fn topological_sort<N, E>(graph: &Graph<N, E>) -> Option<Vec<u32>> {
+    let mut in_degree: HashMap<u32, usize> = graph.adj.keys().map(|v| (*v, 0)).collect();
+    for edges in graph.adj.values() {
+        for edge in edges.iter() {
+            *in_degree.get_mut(&edge.to).unwrap() += 1;
+        }
+    }
+    let mut ready: BTreeSet<u32> = in_degree
+        .iter()
+        .filter(|(_, degree)| **degree == 0)
+        .map(|(vert, _)| *vert)
+        .collect();
+    let mut result = Vec::with_capacity(graph.adj.len());
+    while let Some(vert) = ready.pop_first() {
+        result.push(vert);
+        for edge in graph.edges_from(vert) {
+            let degree = in_degree.get_mut(&edge.to).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                ready.insert(edge.to);
+            }
+        }
+    }
+    if result.len() == graph.adj.len() {
+        Option::Some(result)
+    } else {
+        Option::None
+    }
+}
+
+type LongestEntry<'a, E> = (u64, Option<&'a Edge<E>>);
+
+fn longest_path_ending_at<'a, N, E>(
+    graph: &'a Graph<N, E>,
+    order: &[u32],
+    start: Option<u32>,
+    weight: impl Fn(&E) -> u64,
+) -> HashMap<u32, LongestEntry<'a, E>> {
+    let mut best: HashMap<u32, LongestEntry<E>> = HashMap::new();
+    for vert in order.iter() {
+        if start.is_none() || start == Option::Some(*vert) {
+            best.entry(*vert).or_insert((0, Option::None));
+        }
+        let dist = match best.get(vert) {
+            None => continue,
+            Some((dist, _)) => *dist,
+        };
+        for edge in graph.edges_from(*vert) {
+            let dist_through = dist + weight(&edge.value);
+            let entry = best.entry(edge.to).or_insert((0, Option::None));
+            if entry.1.is_none() || dist_through > entry.0 {
+                *entry = (dist_through, Option::Some(edge));
+            }
+        }
+    }
+    best
+}
+
+fn trace_back<E: Clone>(best: &HashMap<u32, LongestEntry<E>>, end: u32) -> Path<u32, Edge<E>, u64> {
+    let mut vertices = vec![end];
+    let mut steps = Vec::new();
+    let mut curr = end;
+    while let Some(edge) = best[&curr].1 {
+        steps.push(edge.clone());
+        vertices.push(edge.from);
+        curr = edge.from;
+    }
+    vertices.reverse();
+    steps.reverse();
+    Path::new(vertices, steps, best[&end].0)
+}
+
+pub fn longest_path_dag(graph: &Graph) -> Option<Path<u32, Edge, u64>> {
+    longest_path_dag_by(graph, |value| value.unwrap_or(1) as u64)
+}
+
+pub fn longest_path_dag_by<N, E: Clone>(
+    graph: &Graph<N, E>,
+    weight: impl Fn(&E) -> u64,
+) -> Option<Path<u32, Edge<E>, u64>> {
+    let order = topological_sort(graph)?;
+    let best = longest_path_ending_at(graph, &order, Option::None, weight);
+    let end = order
+        .iter()
+        .copied()
+        .max_by_key(|vert| (best[vert].0, std::cmp::Reverse(*vert)))?;
+    Option::Some(trace_back(&best, end))
+}
+
+pub fn longest_path_dag_between(
+    graph: &Graph,
+    start: u32,
+    end: u32,
+) -> Option<Path<u32, Edge, u64>> {
+    longest_path_dag_between_by(graph, start, end, |value| value.unwrap_or(1) as u64)
+}
+
+pub fn longest_path_dag_between_by<N, E: Clone>(
+    graph: &Graph<N, E>,
+    start: u32,
+    end: u32,
+    weight: impl Fn(&E) -> u64,
+) -> Option<Path<u32, Edge<E>, u64>> {
+    if !graph.contains_vertex(start) {
+        return Option::None;
+    }
+    let order = topological_sort(graph)?;
+    let best = longest_path_ending_at(graph, &order, Option::Some(start), weight);
+    if !best.contains_key(&end) {
+        return Option::None;
+    }
+    Option::Some(trace_back(&best, end))
+}
+
+pub struct Schedule {
+    order: Vec<u32>,
+    duration: HashMap<u32, u64>,
+    earliest_start: HashMap<u32, u64>,
+    latest_start: HashMap<u32, u64>,
+    makespan: u64,
+    critical_chain: Vec<u32>,
+}
+
+impl Schedule {
+    pub fn order(&self) -> &Vec<u32> {
+        &self.order
+    }
+
+    pub fn makespan(&self) -> u64 {
+        self.makespan
+    }
+
+    pub fn duration(&self, task: u32) -> Option<u64> {
+        self.duration.get(&task).copied()
+    }
+
+    pub fn earliest_start(&self, task: u32) -> Option<u64> {
+        self.earliest_start.get(&task).copied()
+    }
+
+    pub fn earliest_finish(&self, task: u32) -> Option<u64> {
+        Option::Some(self.earliest_start(task)? + self.duration(task)?)
+    }
+
+    pub fn latest_start(&self, task: u32) -> Option<u64> {
+        self.latest_start.get(&task).copied()
+    }
+
+    pub fn latest_finish(&self, task: u32) -> Option<u64> {
+        Option::Some(self.latest_start(task)? + self.duration(task)?)
+    }
+
+    pub fn slack(&self, task: u32) -> Option<u64> {
+        Option::Some(self.latest_start(task)? - self.earliest_start(task)?)
+    }
+
+    pub fn is_critical(&self, task: u32) -> bool {
+        self.slack(task) == Option::Some(0)
+    }
+
+    pub fn critical_chain(&self) -> &Vec<u32> {
+        &self.critical_chain
+    }
+}
+
+pub fn critical_path<N, E>(
+    graph: &Graph<N, E>,
+    duration: impl Fn(u32, &N) -> u64,
+) -> Option<Schedule> {
+    let order = topological_sort(graph)?;
+    let duration: HashMap<u32, u64> = graph
+        .payloads
+        .iter()
+        .map(|(vert, payload)| (*vert, duration(*vert, payload)))
+        .collect();
+    let mut earliest_start: HashMap<u32, u64> = order.iter().map(|v| (*v, 0)).collect();
+    let mut makespan = 0;
+    for vert in order.iter() {
+        let finish = earliest_start[vert] + duration[vert];
+        makespan = makespan.max(finish);
+        for edge in graph.edges_from(*vert) {
+            let start = earliest_start.get_mut(&edge.to).unwrap();
+            *start = (*start).max(finish);
+        }
+    }
+    let mut latest_start: HashMap<u32, u64> = HashMap::new();
+    for vert in order.iter().rev() {
+        let latest_finish = graph
+            .edges_from(*vert)
+            .map(|edge| latest_start[&edge.to])
+            .min()
+            .unwrap_or(makespan);
+        latest_start.insert(*vert, latest_finish - duration[vert]);
+    }
+    let is_critical = |vert: &u32| earliest_start[vert] == latest_start[vert];
+    let mut critical_chain = Vec::new();
+    let mut curr = order
+        .iter()
+        .copied()
+        .filter(|v| earliest_start[v] == 0 && is_critical(v))
+        .min();
+    while let Some(vert) = curr {
+        critical_chain.push(vert);
+        let finish = earliest_start[&vert] + duration[&vert];
+        curr = graph
+            .edges_from(vert)
+            .map(|edge| edge.to)
+            .filter(|next| is_critical(next) && earliest_start[next] == finish)
+            .min();
+    }
+    Option::Some(Schedule {
+        order,
+        duration,
+        earliest_start,
+        latest_start,
+        makespan,
+        critical_chain,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::directed_weighted;
+
+    fn dag() -> Graph {
+        let mut graph = directed_weighted(&[(1, 2, 2), (2, 3, 2), (1, 3, 5), (3, 4, 1)]);
+        graph.add_vertex(5);
+        graph
+    }
+
+    #[test]
+    fn topological_sort_breaks_ties_by_id_and_rejects_cycles() {
+        let mut graph = dag();
+        assert_eq!(topological_sort(&graph), Option::Some(vec![1, 2, 3, 4, 5]));
+        graph.add_adj(Edge::new(4, 2));
+        assert_eq!(topological_sort(&graph), Option::None);
+        assert!(longest_path_dag(&graph).is_none());
+    }
+
+    #[test]
+    fn longest_paths_follow_the_heaviest_chain() {
+        let graph = dag();
+        let path = longest_path_dag(&graph).unwrap();
+        assert_eq!(path.vertices(), &vec![1, 3, 4]);
+        assert_eq!(path.cost(), 6);
+        let path = longest_path_dag_between(&graph, 2, 4).unwrap();
+        assert_eq!(path.vertices(), &vec![2, 3, 4]);
+        assert_eq!(path.cost(), 3);
+        let single = longest_path_dag_between(&graph, 5, 5).unwrap();
+        assert_eq!(single.hops(), 0);
+        assert!(longest_path_dag_between(&graph, 2, 1).is_none());
+        assert!(longest_path_dag_between(&graph, 9, 1).is_none());
+    }
+
+    #[test]
+    fn schedule_reports_slack_and_critical_chain() {
+        let mut graph: Graph<u64> = Graph::new();
+        for (task, duration) in [(1, 3), (2, 2), (3, 4), (4, 1)].iter() {
+            graph.insert_vertex(*task, *duration);
+        }
+        for (from, to) in [(1, 2), (1, 3), (2, 4), (3, 4)].iter() {
+            graph.add_adj(Edge::new(*from, *to));
+        }
+        let schedule = critical_path(&graph, |_, duration| *duration).unwrap();
+        assert_eq!(schedule.order(), &vec![1, 2, 3, 4]);
+        assert_eq!(schedule.makespan(), 8);
+        assert_eq!(schedule.earliest_start(4), Option::Some(7));
+        assert_eq!(schedule.earliest_finish(3), Option::Some(7));
+        assert_eq!(schedule.latest_start(2), Option::Some(5));
+        assert_eq!(schedule.latest_finish(2), Option::Some(7));
+        assert_eq!(schedule.slack(2), Option::Some(2));
+        assert!(!schedule.is_critical(2));
+        assert!(schedule.is_critical(3));
+        assert_eq!(schedule.critical_chain(), &vec![1, 3, 4]);
+        assert_eq!(schedule.duration(9), Option::None);
+    }
+}
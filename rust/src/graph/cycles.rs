@@ -0,0 +1,297 @@
+use crate::graph::{EdgeId, Graph};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+pub struct SimpleCycles {
+    successors: BTreeMap<u32, Vec<u32>>,
+    predecessors: BTreeMap<u32, Vec<u32>>,
+    order: Vec<u32>,
+    next_start: usize,
+    max_length: Option<usize>,
+    start: u32,
+    component: HashSet<u32>,
+    path: Vec<u32>,
+    blocked: HashSet<u32>,
+    closed: HashSet<u32>,
+    blocking: HashMap<u32, HashSet<u32>>,
+    stack: Vec<(u32, Vec<u32>)>,
+}
+
+impl SimpleCycles {
+    pub fn new<N, E>(graph: &Graph<N, E>, max_length: Option<usize>) -> SimpleCycles {
+        let mut successors: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        let mut predecessors: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        for vert in graph.adj.keys() {
+            successors.insert(*vert, Vec::new());
+            predecessors.insert(*vert, Vec::new());
+        }
+        for (vert, edges) in graph.adj.iter() {
+            for edge in edges.iter() {
+                let targets = successors.get_mut(vert).unwrap();
+                if !targets.contains(&edge.to) {
+                    targets.push(edge.to);
+                    predecessors.get_mut(&edge.to).unwrap().push(*vert);
+                }
+            }
+        }
+        SimpleCycles {
+            order: successors.keys().copied().collect(),
+            successors,
+            predecessors,
+            next_start: 0,
+            max_length,
+            start: 0,
+            component: HashSet::new(),
+            path: Vec::new(),
+            blocked: HashSet::new(),
+            closed: HashSet::new(),
+            blocking: HashMap::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    fn reachable(&self, links: &BTreeMap<u32, Vec<u32>>, start: u32) -> HashSet<u32> {
+        let mut result = HashSet::new();
+        result.insert(start);
+        let mut stack = vec![start];
+        while let Some(vert) = stack.pop() {
+            for next in links[&vert].iter() {
+                if *next >= start && result.insert(*next) {
+                    stack.push(*next);
+                }
+            }
+        }
+        result
+    }
+
+    fn begin(&mut self, start: u32) {
+        let forward = self.reachable(&self.successors, start);
+        let backward = self.reachable(&self.predecessors, start);
+        self.component = forward.intersection(&backward).copied().collect();
+        self.start = start;
+        self.path = vec![start];
+        self.blocked = HashSet::new();
+        self.blocked.insert(start);
+        self.closed = HashSet::new();
+        self.blocking = HashMap::new();
+        let neighbours = self.neighbours(start);
+        self.stack = vec![(start, neighbours)];
+    }
+
+    fn neighbours(&self, vert: u32) -> Vec<u32> {
+        let mut result: Vec<u32> = self.successors[&vert]
+            .iter()
+            .copied()
+            .filter(|next| self.component.contains(next))
+            .collect();
+        result.sort_unstable_by(|a, b| b.cmp(a));
+        result
+    }
+
+    fn unblock(&mut self, vert: u32) {
+        let mut stack = vec![vert];
+        while let Some(curr) = stack.pop() {
+            if self.blocked.remove(&curr) {
+                if let Some(waiting) = self.blocking.remove(&curr) {
+                    stack.extend(waiting);
+                }
+            }
+        }
+    }
+
+    fn step(&mut self) -> Option<Vec<u32>> {
+        while let Some((vert, neighbours)) = self.stack.last_mut() {
+            let vert = *vert;
+            if let Some(next) = neighbours.pop() {
+                if next == self.start {
+                    self.closed.extend(self.path.iter().copied());
+                    if self.max_length.is_none_or(|max| self.path.len() <= max) {
+                        return Option::Some(self.path.clone());
+                    }
+                    continue;
+                }
+                let within_limit = self.max_length.is_none_or(|max| self.path.len() < max);
+                if !self.blocked.contains(&next) && within_limit {
+                    self.path.push(next);
+                    self.closed.remove(&next);
+                    self.blocked.insert(next);
+                    let neighbours = self.neighbours(next);
+                    self.stack.push((next, neighbours));
+                }
+                continue;
+            }
+            if self.max_length.is_some() {
+                self.blocked.remove(&vert);
+            } else if self.closed.contains(&vert) {
+                self.unblock(vert);
+            } else {
+                for next in self.neighbours(vert) {
+                    self.blocking.entry(next).or_default().insert(vert);
+                }
+            }
+            self.stack.pop();
+            self.path.pop();
+        }
+        Option::None
+    }
+}
+
+impl Iterator for SimpleCycles {
+    type Item = Vec<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(cycle) = self.step() {
+                return Option::Some(cycle);
+            }
+            if self.next_start == self.order.len() {
+                return Option::None;
+            }
+            let start = self.order[self.next_start];
+            self.next_start += 1;
+            self.begin(start);
+        }
+    }
+}
+
+pub fn simple_cycles<N, E>(graph: &Graph<N, E>) -> SimpleCycles {
+    SimpleCycles::new(graph, Option::None)
+}
+
+pub fn simple_cycles_bounded<N, E>(graph: &Graph<N, E>, max_length: usize) -> SimpleCycles {
+    SimpleCycles::new(graph, Option::Some(max_length))
+}
+
+pub fn cycle_basis<N, E>(graph: &Graph<N, E>) -> Option<Vec<Vec<u32>>> {
+    if !graph.is_undirected_graph() {
+        return Option::None;
+    }
+    let mut parent: HashMap<u32, u32> = HashMap::new();
+    let mut depth: HashMap<u32, usize> = HashMap::new();
+    let mut tree_edges: HashSet<EdgeId> = HashSet::new();
+    for root in graph.adj.keys() {
+        if depth.contains_key(root) {
+            continue;
+        }
+        depth.insert(*root, 0);
+        let mut fifo = VecDeque::new();
+        fifo.push_back(*root);
+        while let Some(vert) = fifo.pop_front() {
+            for edge in graph.edges_from(vert) {
+                if !depth.contains_key(&edge.to) {
+                    depth.insert(edge.to, depth[&vert] + 1);
+                    parent.insert(edge.to, vert);
+                    if let Some(id) = edge.id {
+                        tree_edges.insert(id);
+                    }
+                    fifo.push_back(edge.to);
+                }
+            }
+        }
+    }
+    let mut seen: BTreeSet<EdgeId> = BTreeSet::new();
+    let mut result = Vec::new();
+    for edges in graph.adj.values() {
+        for edge in edges.iter() {
+            let id = match edge.id {
+                None => continue,
+                Some(id) => id,
+            };
+            if tree_edges.contains(&id) || !seen.insert(id) {
+                continue;
+            }
+            let (mut first, mut second) = (edge.from, edge.to);
+            let mut left = vec![first];
+            let mut right = Vec::new();
+            while first != second {
+                if depth[&first] >= depth[&second] {
+                    first = parent[&first];
+                    left.push(first);
+                } else {
+                    right.push(second);
+                    second = parent[&second];
+                }
+            }
+            left.extend(right.into_iter().rev());
+            result.push(left);
+        }
+    }
+    Option::Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::{directed, undirected};
+    use crate::graph::Edge;
+
+    fn sorted(cycles: impl Iterator<Item = Vec<u32>>) -> Vec<Vec<u32>> {
+        let mut result: Vec<Vec<u32>> = cycles.collect();
+        result.sort();
+        result
+    }
+
+    #[test]
+    fn enumerates_every_elementary_circuit() {
+        let graph = directed(&[(1, 2), (2, 1), (2, 3), (3, 1), (3, 3), (3, 4)]);
+        let cycles = sorted(simple_cycles(&graph));
+        assert_eq!(cycles, vec![vec![1, 2], vec![1, 2, 3], vec![3]]);
+    }
+
+    #[test]
+    fn complete_digraph_has_every_circuit() {
+        let mut edges = Vec::new();
+        for from in 0..4 {
+            for to in 0..4 {
+                if from != to {
+                    edges.push((from, to));
+                }
+            }
+        }
+        let graph = directed(&edges);
+        assert_eq!(simple_cycles(&graph).count(), 20);
+        assert_eq!(simple_cycles_bounded(&graph, 2).count(), 6);
+        assert_eq!(simple_cycles_bounded(&graph, 3).count(), 14);
+    }
+
+    #[test]
+    fn bounded_length_applies_to_closing_edges() {
+        let graph = directed(&[(1, 1), (1, 2), (2, 1)]);
+        assert_eq!(simple_cycles_bounded(&graph, 0).count(), 0);
+        assert_eq!(sorted(simple_cycles_bounded(&graph, 1)), vec![vec![1]]);
+        assert_eq!(simple_cycles_bounded(&graph, 2).count(), 2);
+    }
+
+    #[test]
+    fn cycle_basis_has_one_cycle_per_non_tree_edge() {
+        let graph = undirected(&[
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 1),
+            (1, 3),
+            (5, 6),
+            (6, 7),
+            (7, 5),
+            (8, 9),
+        ]);
+        let basis = cycle_basis(&graph).unwrap();
+        assert_eq!(basis.len(), 3);
+        for cycle in basis.iter() {
+            assert!(cycle.len() >= 3);
+            for (from, to) in cycle.iter().zip(cycle.iter().cycle().skip(1)) {
+                assert!(graph.edge(*from, *to).is_some());
+            }
+        }
+        assert!(cycle_basis(&undirected(&[(1, 2), (2, 3)]))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn cycle_basis_rejects_directed_graphs() {
+        assert!(cycle_basis(&directed(&[(1, 2), (2, 3), (3, 1)])).is_none());
+        let mut mixed = directed(&[(1, 2)]);
+        mixed.add_adj_both(Edge::new(2, 3));
+        assert!(cycle_basis(&mixed).is_none());
+    }
+}
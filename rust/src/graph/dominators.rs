@@ -0,0 +1,299 @@
+use crate::graph::{Edge, Graph};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+pub struct DominatorTree {
+    entry: u32,
+    index: HashMap<u32, usize>,
+    vertices: Vec<u32>,
+    predecessors: Vec<Vec<usize>>,
+    idom: Vec<usize>,
+    children: Vec<Vec<usize>>,
+    interval: Vec<(usize, usize)>,
+}
+
+impl DominatorTree {
+    pub fn new<N, E>(graph: &Graph<N, E>, entry: u32) -> Option<DominatorTree> {
+        if !graph.contains_vertex(entry) {
+            return Option::None;
+        }
+        let mut index: HashMap<u32, usize> = HashMap::new();
+        let mut vertices: Vec<u32> = Vec::new();
+        let mut visited: HashSet<u32> = HashSet::new();
+        let mut stack: Vec<(u32, usize)> = vec![(entry, 0)];
+        visited.insert(entry);
+        while let Some((vert, position)) = stack.last_mut() {
+            let vert = *vert;
+            let edges = graph.adj.get(&vert).unwrap().iter().as_slice();
+            if let Some(edge) = edges.get(*position) {
+                *position += 1;
+                if visited.insert(edge.to) {
+                    stack.push((edge.to, 0));
+                }
+            } else {
+                stack.pop();
+                index.insert(vert, vertices.len());
+                vertices.push(vert);
+            }
+        }
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+        for (from_idx, vert) in vertices.iter().enumerate() {
+            for edge in graph.adj.get(vert).unwrap().iter() {
+                let to_idx = *index.get(&edge.to).unwrap();
+                if !predecessors[to_idx].contains(&from_idx) {
+                    predecessors[to_idx].push(from_idx);
+                }
+            }
+        }
+        let idom = DominatorTree::immediate_dominators(&predecessors);
+        let mut result = DominatorTree {
+            entry,
+            index,
+            vertices,
+            predecessors,
+            idom,
+            children: Vec::new(),
+            interval: Vec::new(),
+        };
+        result.number_tree();
+        Option::Some(result)
+    }
+
+    fn immediate_dominators(predecessors: &[Vec<usize>]) -> Vec<usize> {
+        let undefined = usize::MAX;
+        let root = predecessors.len() - 1;
+        let mut idom = vec![undefined; predecessors.len()];
+        idom[root] = root;
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for vert in (0..root).rev() {
+                let mut new_idom = undefined;
+                for pred in predecessors[vert].iter() {
+                    if idom[*pred] == undefined {
+                        continue;
+                    }
+                    if new_idom == undefined {
+                        new_idom = *pred;
+                    } else {
+                        new_idom = DominatorTree::intersect(&idom, *pred, new_idom);
+                    }
+                }
+                if idom[vert] != new_idom {
+                    idom[vert] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+        idom
+    }
+
+    fn intersect(idom: &[usize], first: usize, second: usize) -> usize {
+        let mut finger_first = first;
+        let mut finger_second = second;
+        while finger_first != finger_second {
+            while finger_first < finger_second {
+                finger_first = idom[finger_first];
+            }
+            while finger_second < finger_first {
+                finger_second = idom[finger_second];
+            }
+        }
+        finger_first
+    }
+
+    fn number_tree(&mut self) {
+        let root = self.vertices.len() - 1;
+        self.children = vec![Vec::new(); self.vertices.len()];
+        for vert in 0..root {
+            self.children[self.idom[vert]].push(vert);
+        }
+        self.interval = vec![(0, 0); self.vertices.len()];
+        let mut counter = 0;
+        let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+        while let Some((vert, position)) = stack.last_mut() {
+            let vert = *vert;
+            if *position == 0 {
+                self.interval[vert].0 = counter;
+                counter += 1;
+            }
+            if let Some(child) = self.children[vert].get(*position) {
+                *position += 1;
+                stack.push((*child, 0));
+            } else {
+                stack.pop();
+                self.interval[vert].1 = counter;
+                counter += 1;
+            }
+        }
+    }
+
+    pub fn entry(&self) -> u32 {
+        self.entry
+    }
+
+    pub fn is_reachable(&self, vert: u32) -> bool {
+        self.index.contains_key(&vert)
+    }
+
+    pub fn immediate_dominator(&self, vert: u32) -> Option<u32> {
+        let idx = *self.index.get(&vert)?;
+        if vert == self.entry {
+            Option::None
+        } else {
+            Option::Some(self.vertices[self.idom[idx]])
+        }
+    }
+
+    pub fn dominates(&self, dominator: u32, vert: u32) -> bool {
+        match (self.index.get(&dominator), self.index.get(&vert)) {
+            (Some(dom_idx), Some(vert_idx)) => {
+                let (dom_in, dom_out) = self.interval[*dom_idx];
+                let (vert_in, vert_out) = self.interval[*vert_idx];
+                dom_in <= vert_in && vert_out <= dom_out
+            }
+            _ => false,
+        }
+    }
+
+    pub fn strictly_dominates(&self, dominator: u32, vert: u32) -> bool {
+        dominator != vert && self.dominates(dominator, vert)
+    }
+
+    pub fn dominators(&self, vert: u32) -> Vec<u32> {
+        let mut result = Vec::new();
+        if let Some(idx) = self.index.get(&vert) {
+            let mut curr = *idx;
+            result.push(self.vertices[curr]);
+            while self.idom[curr] != curr {
+                curr = self.idom[curr];
+                result.push(self.vertices[curr]);
+            }
+        }
+        result
+    }
+
+    pub fn children(&self, vert: u32) -> Vec<u32> {
+        match self.index.get(&vert) {
+            None => Vec::new(),
+            Some(idx) => self.children[*idx]
+                .iter()
+                .map(|child| self.vertices[*child])
+                .collect(),
+        }
+    }
+
+    pub fn tree(&self) -> Graph {
+        let mut result = Graph::new();
+        result.add_vertex(self.entry);
+        for (vert, children) in self.children.iter().enumerate() {
+            for child in children.iter() {
+                result.add_adj(Edge::new(self.vertices[vert], self.vertices[*child]));
+            }
+        }
+        result
+    }
+
+    pub fn dominance_frontiers(&self) -> BTreeMap<u32, Vec<u32>> {
+        let mut frontiers: Vec<Vec<usize>> = vec![Vec::new(); self.vertices.len()];
+        let entry = self.index[&self.entry];
+        for (vert, preds) in self.predecessors.iter().enumerate() {
+            let implicit = if vert == entry { 1 } else { 0 };
+            if preds.len() + implicit < 2 {
+                continue;
+            }
+            for pred in preds.iter() {
+                let mut runner = *pred;
+                while vert == entry || runner != self.idom[vert] {
+                    if !frontiers[runner].contains(&vert) {
+                        frontiers[runner].push(vert);
+                    }
+                    if runner == entry {
+                        break;
+                    }
+                    runner = self.idom[runner];
+                }
+            }
+        }
+        let mut result = BTreeMap::new();
+        for (vert, frontier) in frontiers.iter().enumerate() {
+            let members = frontier.iter().map(|v| self.vertices[*v]).collect();
+            result.insert(self.vertices[vert], members);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::directed;
+
+    #[test]
+    fn back_edges_into_the_entry_join_at_the_entry() {
+        let tree = DominatorTree::new(&directed(&[(1, 2), (2, 1)]), 1).unwrap();
+        let frontiers = tree.dominance_frontiers();
+        assert_eq!(frontiers[&1], vec![1]);
+        assert_eq!(frontiers[&2], vec![1]);
+        let tree = DominatorTree::new(&directed(&[(1, 1), (1, 2)]), 1).unwrap();
+        let frontiers = tree.dominance_frontiers();
+        assert_eq!(frontiers[&1], vec![1]);
+        assert!(frontiers[&2].is_empty());
+    }
+
+    #[test]
+    fn missing_entry_has_no_tree() {
+        let graph = directed(&[(1, 2)]);
+        assert!(DominatorTree::new(&graph, 7).is_none());
+    }
+
+    #[test]
+    fn dominators_of_a_loop_with_a_diamond() {
+        let graph = directed(&[
+            (1, 2),
+            (2, 3),
+            (2, 4),
+            (3, 5),
+            (4, 5),
+            (5, 2),
+            (5, 6),
+            (7, 6),
+        ]);
+        let tree = DominatorTree::new(&graph, 1).unwrap();
+        assert_eq!(tree.entry(), 1);
+        assert_eq!(tree.immediate_dominator(1), Option::None);
+        assert_eq!(tree.immediate_dominator(3), Option::Some(2));
+        assert_eq!(tree.immediate_dominator(5), Option::Some(2));
+        assert_eq!(tree.immediate_dominator(6), Option::Some(5));
+        assert!(!tree.is_reachable(7));
+        assert_eq!(tree.immediate_dominator(7), Option::None);
+        assert_eq!(tree.dominators(6), vec![6, 5, 2, 1]);
+        assert!(tree.dominates(2, 6));
+        assert!(tree.dominates(6, 6));
+        assert!(!tree.strictly_dominates(6, 6));
+        assert!(!tree.dominates(3, 5));
+        let mut children = tree.children(2);
+        children.sort_unstable();
+        assert_eq!(children, vec![3, 4, 5]);
+        assert_eq!(tree.tree().edge_count(), 5);
+    }
+
+    #[test]
+    fn dominance_frontiers_of_a_diamond_loop() {
+        let graph = directed(&[(1, 2), (2, 3), (2, 4), (3, 5), (4, 5), (5, 2), (5, 6)]);
+        let frontiers = DominatorTree::new(&graph, 1).unwrap().dominance_frontiers();
+        assert_eq!(frontiers[&1], Vec::<u32>::new());
+        assert_eq!(frontiers[&2], vec![2]);
+        assert_eq!(frontiers[&3], vec![5]);
+        assert_eq!(frontiers[&4], vec![5]);
+        assert_eq!(frontiers[&5], vec![2]);
+        assert_eq!(frontiers[&6], Vec::<u32>::new());
+    }
+
+    #[test]
+    fn deep_chains_do_not_overflow() {
+        let edges: Vec<(u32, u32)> = (0..100_000).map(|v| (v, v + 1)).collect();
+        let tree = DominatorTree::new(&directed(&edges), 0).unwrap();
+        assert_eq!(tree.immediate_dominator(100_000), Option::Some(99_999));
+        assert!(tree.dominates(0, 100_000));
+    }
+}
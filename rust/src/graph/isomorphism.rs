@@ -0,0 +1,453 @@
+use crate::graph::{Edge, Graph};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum MatchMode {
+    Isomorphism,
+    InducedSubgraph,
+    Monomorphism,
+}
+
+struct IndexedGraph<'a, E> {
+    vertices: Vec<u32>,
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+    edges: HashMap<(usize, usize), Vec<&'a Edge<E>>>,
+}
+
+impl<'a, E> IndexedGraph<'a, E> {
+    fn new<N>(graph: &'a Graph<N, E>) -> IndexedGraph<'a, E> {
+        let mut vertices: Vec<u32> = graph.adj.keys().copied().collect();
+        vertices.sort_unstable();
+        let mut index = HashMap::new();
+        for (idx, vert) in vertices.iter().enumerate() {
+            index.insert(*vert, idx);
+        }
+        let mut successors = vec![Vec::new(); vertices.len()];
+        let mut predecessors = vec![Vec::new(); vertices.len()];
+        let mut edges: HashMap<(usize, usize), Vec<&'a Edge<E>>> = HashMap::new();
+        for (from, vert) in vertices.iter().enumerate() {
+            for edge in graph.adj.get(vert).unwrap().iter() {
+                let to = *index.get(&edge.to).unwrap();
+                let parallel = edges.entry((from, to)).or_default();
+                if parallel.is_empty() {
+                    successors[from].push(to);
+                    predecessors[to].push(from);
+                }
+                parallel.push(edge);
+            }
+        }
+        IndexedGraph {
+            vertices,
+            successors,
+            predecessors,
+            edges,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges.values().map(|parallel| parallel.len()).sum()
+    }
+
+    fn between(&self, from: usize, to: usize) -> &[&'a Edge<E>] {
+        match self.edges.get(&(from, to)) {
+            None => &[],
+            Some(parallel) => parallel.as_slice(),
+        }
+    }
+}
+
+struct MatchState {
+    core: Vec<Option<usize>>,
+    in_depth: Vec<usize>,
+    out_depth: Vec<usize>,
+}
+
+impl MatchState {
+    fn new(size: usize) -> MatchState {
+        MatchState {
+            core: vec![Option::None; size],
+            in_depth: vec![0; size],
+            out_depth: vec![0; size],
+        }
+    }
+
+    fn push<E>(&mut self, graph: &IndexedGraph<E>, vert: usize, other: usize, depth: usize) {
+        self.core[vert] = Option::Some(other);
+        if self.in_depth[vert] == 0 {
+            self.in_depth[vert] = depth;
+        }
+        if self.out_depth[vert] == 0 {
+            self.out_depth[vert] = depth;
+        }
+        for pred in graph.predecessors[vert].iter() {
+            if self.in_depth[*pred] == 0 {
+                self.in_depth[*pred] = depth;
+            }
+        }
+        for succ in graph.successors[vert].iter() {
+            if self.out_depth[*succ] == 0 {
+                self.out_depth[*succ] = depth;
+            }
+        }
+    }
+
+    fn pop(&mut self, vert: usize, depth: usize) {
+        self.core[vert] = Option::None;
+        for d in self.in_depth.iter_mut() {
+            if *d == depth {
+                *d = 0;
+            }
+        }
+        for d in self.out_depth.iter_mut() {
+            if *d == depth {
+                *d = 0;
+            }
+        }
+    }
+
+    fn terminal_in(&self, vert: usize) -> bool {
+        self.core[vert].is_none() && self.in_depth[vert] > 0
+    }
+
+    fn terminal_out(&self, vert: usize) -> bool {
+        self.core[vert].is_none() && self.out_depth[vert] > 0
+    }
+
+    fn neighbourhood(&self, neighbours: &[usize]) -> (usize, usize, usize) {
+        let mut terminal_in = 0;
+        let mut terminal_out = 0;
+        let mut remote = 0;
+        for vert in neighbours.iter() {
+            if self.core[*vert].is_some() {
+                continue;
+            }
+            if self.terminal_in(*vert) {
+                terminal_in += 1;
+            }
+            if self.terminal_out(*vert) {
+                terminal_out += 1;
+            }
+            if !self.terminal_in(*vert) && !self.terminal_out(*vert) {
+                remote += 1;
+            }
+        }
+        (terminal_in, terminal_out, remote)
+    }
+}
+
+type EdgeMatch<'a, E> = Box<dyn Fn(&Edge<E>, &Edge<E>) -> bool + 'a>;
+
+pub struct Vf2Matcher<'a, E = Option<u32>> {
+    pattern: IndexedGraph<'a, E>,
+    target: IndexedGraph<'a, E>,
+    mode: MatchMode,
+    edge_match: Option<EdgeMatch<'a, E>>,
+}
+
+impl<'a, E> Vf2Matcher<'a, E> {
+    pub fn new<P, T>(
+        pattern: &'a Graph<P, E>,
+        target: &'a Graph<T, E>,
+        mode: MatchMode,
+    ) -> Vf2Matcher<'a, E> {
+        Vf2Matcher {
+            pattern: IndexedGraph::new(pattern),
+            target: IndexedGraph::new(target),
+            mode,
+            edge_match: Option::None,
+        }
+    }
+
+    pub fn with_edge_match(mut self, edge_match: impl Fn(&Edge<E>, &Edge<E>) -> bool + 'a) -> Self {
+        self.edge_match = Option::Some(Box::new(edge_match));
+        self
+    }
+
+    pub fn first(&self) -> Option<HashMap<u32, u32>> {
+        let mut result = Option::None;
+        self.search(&mut |mapping| {
+            result = Option::Some(mapping);
+            false
+        });
+        result
+    }
+
+    pub fn all(&self) -> Vec<HashMap<u32, u32>> {
+        let mut result = Vec::new();
+        self.search(&mut |mapping| {
+            result.push(mapping);
+            true
+        });
+        result
+    }
+
+    pub fn exists(&self) -> bool {
+        self.first().is_some()
+    }
+
+    fn search(&self, visit: &mut dyn FnMut(HashMap<u32, u32>) -> bool) {
+        let sizes_fit = match self.mode {
+            MatchMode::Isomorphism => {
+                self.pattern.len() == self.target.len()
+                    && self.pattern.edge_count() == self.target.edge_count()
+            }
+            _ => {
+                self.pattern.len() <= self.target.len()
+                    && self.pattern.edge_count() <= self.target.edge_count()
+            }
+        };
+        if !sizes_fit {
+            return;
+        }
+        let mut pattern_state = MatchState::new(self.pattern.len());
+        let mut target_state = MatchState::new(self.target.len());
+        self.match_recursive(&mut pattern_state, &mut target_state, 1, visit);
+    }
+
+    fn match_recursive(
+        &self,
+        pattern_state: &mut MatchState,
+        target_state: &mut MatchState,
+        depth: usize,
+        visit: &mut dyn FnMut(HashMap<u32, u32>) -> bool,
+    ) -> bool {
+        if depth > self.pattern.len() {
+            let mut mapping = HashMap::new();
+            for (vert, other) in pattern_state.core.iter().enumerate() {
+                mapping.insert(
+                    self.pattern.vertices[vert],
+                    self.target.vertices[other.unwrap()],
+                );
+            }
+            return visit(mapping);
+        }
+        let (vert, candidates) = self.candidates(pattern_state, target_state);
+        for other in candidates {
+            if !self.feasible(pattern_state, target_state, vert, other) {
+                continue;
+            }
+            pattern_state.push(&self.pattern, vert, other, depth);
+            target_state.push(&self.target, other, vert, depth);
+            let go_on = self.match_recursive(pattern_state, target_state, depth + 1, visit);
+            pattern_state.pop(vert, depth);
+            target_state.pop(other, depth);
+            if !go_on {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn candidates(
+        &self,
+        pattern_state: &MatchState,
+        target_state: &MatchState,
+    ) -> (usize, Vec<usize>) {
+        let pattern_out = (0..self.pattern.len()).find(|v| pattern_state.terminal_out(*v));
+        let target_out: Vec<usize> = (0..self.target.len())
+            .filter(|v| target_state.terminal_out(*v))
+            .collect();
+        if let Some(vert) = pattern_out {
+            if !target_out.is_empty() {
+                return (vert, target_out);
+            }
+        }
+        let pattern_in = (0..self.pattern.len()).find(|v| pattern_state.terminal_in(*v));
+        let target_in: Vec<usize> = (0..self.target.len())
+            .filter(|v| target_state.terminal_in(*v))
+            .collect();
+        if let Some(vert) = pattern_in {
+            if !target_in.is_empty() {
+                return (vert, target_in);
+            }
+        }
+        let vert = (0..self.pattern.len())
+            .find(|v| pattern_state.core[*v].is_none())
+            .unwrap();
+        let unmapped = (0..self.target.len())
+            .filter(|v| target_state.core[*v].is_none())
+            .collect();
+        (vert, unmapped)
+    }
+
+    fn edges_compatible(&self, pattern_edges: &[&Edge<E>], target_edges: &[&Edge<E>]) -> bool {
+        let counts_fit = match self.mode {
+            MatchMode::Monomorphism => pattern_edges.len() <= target_edges.len(),
+            _ => pattern_edges.len() == target_edges.len(),
+        };
+        if !counts_fit {
+            return false;
+        }
+        match &self.edge_match {
+            None => true,
+            Some(edge_match) => pattern_edges
+                .iter()
+                .all(|p| target_edges.iter().any(|t| edge_match(p, t))),
+        }
+    }
+
+    fn feasible(
+        &self,
+        pattern_state: &MatchState,
+        target_state: &MatchState,
+        vert: usize,
+        other: usize,
+    ) -> bool {
+        let loops = self.pattern.between(vert, vert);
+        let target_loops = self.target.between(other, other);
+        if !self.edges_compatible(loops, target_loops) {
+            return false;
+        }
+        for succ in self.pattern.successors[vert].iter() {
+            if let Some(mapped) = pattern_state.core[*succ] {
+                let target_edges = self.target.between(other, mapped);
+                if !self.edges_compatible(self.pattern.between(vert, *succ), target_edges) {
+                    return false;
+                }
+            }
+        }
+        for pred in self.pattern.predecessors[vert].iter() {
+            if let Some(mapped) = pattern_state.core[*pred] {
+                let target_edges = self.target.between(mapped, other);
+                if !self.edges_compatible(self.pattern.between(*pred, vert), target_edges) {
+                    return false;
+                }
+            }
+        }
+        if self.mode == MatchMode::Monomorphism {
+            return true;
+        }
+        for succ in self.target.successors[other].iter() {
+            if let Some(mapped) = target_state.core[*succ] {
+                if self.pattern.between(vert, mapped).is_empty() {
+                    return false;
+                }
+            }
+        }
+        for pred in self.target.predecessors[other].iter() {
+            if let Some(mapped) = target_state.core[*pred] {
+                if self.pattern.between(mapped, vert).is_empty() {
+                    return false;
+                }
+            }
+        }
+        let pattern_succ = pattern_state.neighbourhood(&self.pattern.successors[vert]);
+        let target_succ = target_state.neighbourhood(&self.target.successors[other]);
+        let pattern_pred = pattern_state.neighbourhood(&self.pattern.predecessors[vert]);
+        let target_pred = target_state.neighbourhood(&self.target.predecessors[other]);
+        match self.mode {
+            MatchMode::Isomorphism => pattern_succ == target_succ && pattern_pred == target_pred,
+            _ => {
+                pattern_succ.0 <= target_succ.0
+                    && pattern_succ.1 <= target_succ.1
+                    && pattern_succ.2 <= target_succ.2
+                    && pattern_pred.0 <= target_pred.0
+                    && pattern_pred.1 <= target_pred.1
+                    && pattern_pred.2 <= target_pred.2
+            }
+        }
+    }
+}
+
+pub fn is_isomorphic<P, T, E>(first: &Graph<P, E>, second: &Graph<T, E>) -> bool {
+    Vf2Matcher::new(first, second, MatchMode::Isomorphism).exists()
+}
+
+pub fn find_isomorphism<P, T, E>(
+    first: &Graph<P, E>,
+    second: &Graph<T, E>,
+) -> Option<HashMap<u32, u32>> {
+    Vf2Matcher::new(first, second, MatchMode::Isomorphism).first()
+}
+
+pub fn find_subgraph_isomorphism<P, T, E>(
+    pattern: &Graph<P, E>,
+    target: &Graph<T, E>,
+) -> Option<HashMap<u32, u32>> {
+    Vf2Matcher::new(pattern, target, MatchMode::InducedSubgraph).first()
+}
+
+pub fn find_subgraph_monomorphism<P, T, E>(
+    pattern: &Graph<P, E>,
+    target: &Graph<T, E>,
+) -> Option<HashMap<u32, u32>> {
+    Vf2Matcher::new(pattern, target, MatchMode::Monomorphism).first()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::{directed, undirected_weighted};
+
+    fn preserves_edges(pattern: &Graph, target: &Graph, mapping: &HashMap<u32, u32>) -> bool {
+        pattern.adj.iter().all(|(vert, edges)| {
+            edges
+                .iter()
+                .all(|edge| target.edge(mapping[vert], mapping[&edge.to]).is_some())
+        })
+    }
+
+    #[test]
+    fn relabelled_cycles_are_isomorphic() {
+        let cycle = undirected_weighted(&[(1, 2, 1), (2, 3, 1), (3, 4, 1), (4, 5, 1), (5, 1, 1)]);
+        let relabelled = undirected_weighted(&[
+            (30, 10, 1),
+            (10, 50, 1),
+            (50, 20, 1),
+            (20, 40, 1),
+            (40, 30, 1),
+        ]);
+        let mapping = find_isomorphism(&cycle, &relabelled).unwrap();
+        assert_eq!(mapping.len(), 5);
+        assert!(preserves_edges(&cycle, &relabelled, &mapping));
+        let bowtie = undirected_weighted(&[(1, 2, 1), (2, 3, 1), (3, 1, 1), (3, 4, 1), (4, 5, 1)]);
+        assert!(!is_isomorphic(&cycle, &bowtie));
+    }
+
+    #[test]
+    fn square_has_eight_automorphisms() {
+        let square = undirected_weighted(&[(1, 2, 1), (2, 3, 1), (3, 4, 1), (4, 1, 1)]);
+        let all = Vf2Matcher::new(&square, &square, MatchMode::Isomorphism).all();
+        assert_eq!(all.len(), 8);
+        assert!(all.iter().all(|m| preserves_edges(&square, &square, m)));
+    }
+
+    #[test]
+    fn direction_matters() {
+        let cycle = directed(&[(1, 2), (2, 3), (3, 1)]);
+        let transitive = directed(&[(1, 2), (2, 3), (1, 3)]);
+        assert!(!is_isomorphic(&cycle, &transitive));
+        assert!(is_isomorphic(&cycle, &directed(&[(7, 9), (9, 8), (8, 7)])));
+    }
+
+    #[test]
+    fn induced_subgraphs_differ_from_monomorphisms() {
+        let path = undirected_weighted(&[(1, 2, 1), (2, 3, 1)]);
+        let triangle = undirected_weighted(&[(1, 2, 1), (2, 3, 1), (3, 1, 1)]);
+        assert!(find_subgraph_isomorphism(&path, &triangle).is_none());
+        let mapping = find_subgraph_monomorphism(&path, &triangle).unwrap();
+        assert!(preserves_edges(&path, &triangle, &mapping));
+        let square = undirected_weighted(&[(1, 2, 1), (2, 3, 1), (3, 4, 1), (4, 1, 1)]);
+        let mapping = find_subgraph_isomorphism(&path, &square).unwrap();
+        assert!(preserves_edges(&path, &square, &mapping));
+        assert!(find_subgraph_monomorphism(&triangle, &square).is_none());
+    }
+
+    #[test]
+    fn edge_match_filters_by_weight() {
+        let pattern = undirected_weighted(&[(1, 2, 5), (2, 3, 7)]);
+        let target = undirected_weighted(&[(10, 20, 7), (20, 30, 5), (30, 40, 5)]);
+        let matcher = Vf2Matcher::new(&pattern, &target, MatchMode::Monomorphism)
+            .with_edge_match(|first, second| first.value() == second.value());
+        let all = matcher.all();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0][&1], 30);
+        assert_eq!(all[0][&2], 20);
+        assert_eq!(all[0][&3], 10);
+    }
+}
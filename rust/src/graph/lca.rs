@@ -0,0 +1,355 @@
+use crate::graph::Graph;
+use std::collections::{HashMap, VecDeque};
+
+struct RootedTree {
+    index: HashMap<u32, usize>,
+    vertices: Vec<u32>,
+    parent: Vec<usize>,
+    children: Vec<Vec<usize>>,
+    depth: Vec<usize>,
+    root_distance: Vec<u64>,
+}
+
+impl RootedTree {
+    fn new<N, E>(graph: &Graph<N, E>, root: u32, weight: impl Fn(&E) -> u64) -> RootedTree {
+        let mut tree = RootedTree {
+            index: HashMap::new(),
+            vertices: Vec::new(),
+            parent: Vec::new(),
+            children: Vec::new(),
+            depth: Vec::new(),
+            root_distance: Vec::new(),
+        };
+        tree.push(root, 0, 0, 0);
+        let mut fifo = VecDeque::new();
+        fifo.push_back(root);
+        while let Some(vert) = fifo.pop_front() {
+            let vert_idx = *tree.index.get(&vert).unwrap();
+            if let Some(edges) = graph.adj.get(&vert) {
+                for edge in edges.iter() {
+                    if tree.index.contains_key(&edge.to) {
+                        continue;
+                    }
+                    let child_idx = tree.push(
+                        edge.to,
+                        vert_idx,
+                        tree.depth[vert_idx] + 1,
+                        tree.root_distance[vert_idx] + weight(&edge.value),
+                    );
+                    tree.children[vert_idx].push(child_idx);
+                    fifo.push_back(edge.to);
+                }
+            }
+        }
+        tree
+    }
+
+    fn push(&mut self, vert: u32, parent: usize, depth: usize, root_distance: u64) -> usize {
+        let idx = self.vertices.len();
+        self.index.insert(vert, idx);
+        self.vertices.push(vert);
+        self.parent.push(parent);
+        self.children.push(Vec::new());
+        self.depth.push(depth);
+        self.root_distance.push(root_distance);
+        idx
+    }
+
+    fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn pair(&self, first: u32, second: u32) -> Option<(usize, usize)> {
+        match (self.index.get(&first), self.index.get(&second)) {
+            (Some(first_idx), Some(second_idx)) => Option::Some((*first_idx, *second_idx)),
+            _ => Option::None,
+        }
+    }
+
+    fn depth(&self, vert: u32) -> Option<usize> {
+        self.index.get(&vert).map(|idx| self.depth[*idx])
+    }
+
+    fn distance(&self, first: usize, second: usize, ancestor: usize) -> u64 {
+        self.root_distance[first] + self.root_distance[second] - 2 * self.root_distance[ancestor]
+    }
+
+    fn hops(&self, first: usize, second: usize, ancestor: usize) -> usize {
+        self.depth[first] + self.depth[second] - 2 * self.depth[ancestor]
+    }
+}
+
+fn floor_log2(value: usize) -> usize {
+    (usize::BITS - 1 - value.leading_zeros()) as usize
+}
+
+pub struct BinaryLiftingLca {
+    tree: RootedTree,
+    up: Vec<Vec<usize>>,
+}
+
+impl BinaryLiftingLca {
+    pub fn new(graph: &Graph, root: u32) -> BinaryLiftingLca {
+        BinaryLiftingLca::new_by(graph, root, |value| value.unwrap_or(1) as u64)
+    }
+
+    pub fn new_by<N, E>(
+        graph: &Graph<N, E>,
+        root: u32,
+        weight: impl Fn(&E) -> u64,
+    ) -> BinaryLiftingLca {
+        let tree = RootedTree::new(graph, root, weight);
+        let levels = floor_log2(tree.len()) + 1;
+        let mut up = vec![tree.parent.clone()];
+        for level in 1..levels {
+            let prev = &up[level - 1];
+            let next: Vec<usize> = (0..tree.len()).map(|v| prev[prev[v]]).collect();
+            up.push(next);
+        }
+        BinaryLiftingLca { tree, up }
+    }
+
+    fn lift(&self, vert: usize, steps: usize) -> usize {
+        let mut result = vert;
+        for level in 0..self.up.len() {
+            if steps & (1 << level) != 0 {
+                result = self.up[level][result];
+            }
+        }
+        result
+    }
+
+    fn lca_index(&self, first: usize, second: usize) -> usize {
+        let (mut deeper, mut other) = (first, second);
+        if self.tree.depth[deeper] < self.tree.depth[other] {
+            std::mem::swap(&mut deeper, &mut other);
+        }
+        deeper = self.lift(deeper, self.tree.depth[deeper] - self.tree.depth[other]);
+        if deeper == other {
+            return deeper;
+        }
+        for level in (0..self.up.len()).rev() {
+            if self.up[level][deeper] != self.up[level][other] {
+                deeper = self.up[level][deeper];
+                other = self.up[level][other];
+            }
+        }
+        self.tree.parent[deeper]
+    }
+
+    pub fn lca(&self, first: u32, second: u32) -> Option<u32> {
+        self.tree
+            .pair(first, second)
+            .map(|(f, s)| self.tree.vertices[self.lca_index(f, s)])
+    }
+
+    pub fn kth_ancestor(&self, vert: u32, k: usize) -> Option<u32> {
+        let idx = *self.tree.index.get(&vert)?;
+        if k > self.tree.depth[idx] {
+            return Option::None;
+        }
+        Option::Some(self.tree.vertices[self.lift(idx, k)])
+    }
+
+    pub fn depth(&self, vert: u32) -> Option<usize> {
+        self.tree.depth(vert)
+    }
+
+    pub fn distance(&self, first: u32, second: u32) -> Option<u64> {
+        self.tree
+            .pair(first, second)
+            .map(|(f, s)| self.tree.distance(f, s, self.lca_index(f, s)))
+    }
+
+    pub fn hops(&self, first: u32, second: u32) -> Option<usize> {
+        self.tree
+            .pair(first, second)
+            .map(|(f, s)| self.tree.hops(f, s, self.lca_index(f, s)))
+    }
+}
+
+pub struct EulerTourLca {
+    tree: RootedTree,
+    first_visit: Vec<usize>,
+    sparse: Vec<Vec<usize>>,
+}
+
+impl EulerTourLca {
+    pub fn new(graph: &Graph, root: u32) -> EulerTourLca {
+        EulerTourLca::new_by(graph, root, |value| value.unwrap_or(1) as u64)
+    }
+
+    pub fn new_by<N, E>(
+        graph: &Graph<N, E>,
+        root: u32,
+        weight: impl Fn(&E) -> u64,
+    ) -> EulerTourLca {
+        let tree = RootedTree::new(graph, root, weight);
+        let mut first_visit = vec![0; tree.len()];
+        let mut tour = Vec::with_capacity(2 * tree.len());
+        let mut stack: Vec<(usize, usize)> = vec![(0, 0)];
+        while let Some((vert, child_pos)) = stack.last_mut() {
+            let vert = *vert;
+            if *child_pos == 0 {
+                first_visit[vert] = tour.len();
+            }
+            tour.push(vert);
+            if let Some(child) = tree.children[vert].get(*child_pos) {
+                *child_pos += 1;
+                stack.push((*child, 0));
+            } else {
+                stack.pop();
+            }
+        }
+        let mut sparse = vec![tour];
+        let mut width = 1;
+        while 2 * width <= sparse[0].len() {
+            let prev = sparse.last().unwrap();
+            let next: Vec<usize> = (0..prev.len() - width)
+                .map(|i| {
+                    let (left, right) = (prev[i], prev[i + width]);
+                    if tree.depth[left] <= tree.depth[right] {
+                        left
+                    } else {
+                        right
+                    }
+                })
+                .collect();
+            sparse.push(next);
+            width *= 2;
+        }
+        EulerTourLca {
+            tree,
+            first_visit,
+            sparse,
+        }
+    }
+
+    fn lca_index(&self, first: usize, second: usize) -> usize {
+        let mut from = self.first_visit[first];
+        let mut to = self.first_visit[second];
+        if from > to {
+            std::mem::swap(&mut from, &mut to);
+        }
+        let level = floor_log2(to - from + 1);
+        let left = self.sparse[level][from];
+        let right = self.sparse[level][to + 1 - (1 << level)];
+        if self.tree.depth[left] <= self.tree.depth[right] {
+            left
+        } else {
+            right
+        }
+    }
+
+    pub fn lca(&self, first: u32, second: u32) -> Option<u32> {
+        self.tree
+            .pair(first, second)
+            .map(|(f, s)| self.tree.vertices[self.lca_index(f, s)])
+    }
+
+    pub fn depth(&self, vert: u32) -> Option<usize> {
+        self.tree.depth(vert)
+    }
+
+    pub fn distance(&self, first: u32, second: u32) -> Option<u64> {
+        self.tree
+            .pair(first, second)
+            .map(|(f, s)| self.tree.distance(f, s, self.lca_index(f, s)))
+    }
+
+    pub fn hops(&self, first: u32, second: u32) -> Option<usize> {
+        self.tree
+            .pair(first, second)
+            .map(|(f, s)| self.tree.hops(f, s, self.lca_index(f, s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Edge;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_tree(rng: &mut StdRng, vertices: u32) -> (Graph, Vec<u32>, Vec<u64>) {
+        let mut graph = Graph::new();
+        graph.add_vertex(0);
+        let mut parent = vec![0];
+        let mut distance = vec![0];
+        for vert in 1..vertices {
+            let up = rng.gen_range(0..vert);
+            let weight = rng.gen_range(1..10);
+            graph.add_adj_both(Edge::new_weighted(weight, up, vert));
+            parent.push(up);
+            distance.push(distance[up as usize] + weight as u64);
+        }
+        (graph, parent, distance)
+    }
+
+    fn naive_lca(parent: &[u32], first: u32, second: u32) -> u32 {
+        let mut ancestors = vec![first];
+        let mut curr = first;
+        while curr != 0 {
+            curr = parent[curr as usize];
+            ancestors.push(curr);
+        }
+        let mut curr = second;
+        while !ancestors.contains(&curr) {
+            curr = parent[curr as usize];
+        }
+        curr
+    }
+
+    #[test]
+    fn both_structures_agree_with_a_naive_walk() {
+        let mut rng = StdRng::seed_from_u64(29);
+        for _ in 0..20 {
+            let (graph, parent, distance) = random_tree(&mut rng, 40);
+            let lifting = BinaryLiftingLca::new(&graph, 0);
+            let euler = EulerTourLca::new(&graph, 0);
+            for first in 0..40 {
+                for second in 0..40 {
+                    let ancestor = naive_lca(&parent, first, second);
+                    assert_eq!(lifting.lca(first, second), Option::Some(ancestor));
+                    assert_eq!(euler.lca(first, second), Option::Some(ancestor));
+                    let expected = distance[first as usize] + distance[second as usize]
+                        - 2 * distance[ancestor as usize];
+                    assert_eq!(lifting.distance(first, second), Option::Some(expected));
+                    assert_eq!(euler.distance(first, second), Option::Some(expected));
+                    assert_eq!(lifting.hops(first, second), euler.hops(first, second));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ancestors_and_depths_on_a_chain() {
+        let mut graph: Graph = Graph::new();
+        for vert in 0..100_000 {
+            graph.add_adj_both(Edge::new(vert, vert + 1));
+        }
+        let lifting = BinaryLiftingLca::new(&graph, 0);
+        let euler = EulerTourLca::new(&graph, 0);
+        assert_eq!(lifting.depth(100_000), Option::Some(100_000));
+        assert_eq!(euler.depth(100_000), Option::Some(100_000));
+        assert_eq!(lifting.kth_ancestor(100_000, 99_999), Option::Some(1));
+        assert_eq!(lifting.kth_ancestor(5, 6), Option::None);
+        assert_eq!(lifting.lca(70_000, 123), Option::Some(123));
+        assert_eq!(euler.lca(70_000, 123), Option::Some(123));
+        assert_eq!(euler.hops(3, 70_000), Option::Some(69_997));
+    }
+
+    #[test]
+    fn vertices_outside_the_tree_have_no_answers() {
+        let mut graph: Graph = Graph::new();
+        graph.add_adj_both(Edge::new(1, 2));
+        graph.add_adj_both(Edge::new(3, 4));
+        let lifting = BinaryLiftingLca::new(&graph, 1);
+        let euler = EulerTourLca::new(&graph, 1);
+        assert_eq!(lifting.lca(1, 2), Option::Some(1));
+        assert_eq!(lifting.lca(1, 3), Option::None);
+        assert_eq!(euler.distance(2, 4), Option::None);
+        assert_eq!(lifting.depth(4), Option::None);
+        assert_eq!(lifting.kth_ancestor(9, 0), Option::None);
+    }
+}
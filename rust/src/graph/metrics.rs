@@ -0,0 +1,303 @@
+use crate::graph::{strongly_connected_components, Graph};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+fn bfs_tree<N, E>(graph: &Graph<N, E>, start: u32) -> (HashMap<u32, usize>, HashMap<u32, u32>) {
+    let mut distances = HashMap::new();
+    let mut parents = HashMap::new();
+    distances.insert(start, 0);
+    let mut fifo = VecDeque::new();
+    fifo.push_back(start);
+    while let Some(vert) = fifo.pop_front() {
+        let dist = distances[&vert];
+        for edge in graph.edges_from(vert) {
+            if let Entry::Vacant(entry) = distances.entry(edge.to) {
+                entry.insert(dist + 1);
+                parents.insert(edge.to, vert);
+                fifo.push_back(edge.to);
+            }
+        }
+    }
+    (distances, parents)
+}
+
+fn farthest(distances: &HashMap<u32, usize>) -> (u32, usize) {
+    distances
+        .iter()
+        .map(|(vert, dist)| (*vert, *dist))
+        .max_by_key(|(vert, dist)| (*dist, std::cmp::Reverse(*vert)))
+        .unwrap()
+}
+
+pub fn eccentricity<N, E>(graph: &Graph<N, E>, vert: u32) -> Option<usize> {
+    if !graph.contains_vertex(vert) {
+        return Option::None;
+    }
+    let (distances, _) = bfs_tree(graph, vert);
+    if distances.len() < graph.adj.len() {
+        return Option::None;
+    }
+    Option::Some(farthest(&distances).1)
+}
+
+pub fn eccentricities<N, E>(graph: &Graph<N, E>) -> Option<BTreeMap<u32, usize>> {
+    let mut result = BTreeMap::new();
+    for vert in graph.adj.keys() {
+        result.insert(*vert, eccentricity(graph, *vert)?);
+    }
+    Option::Some(result)
+}
+
+pub fn diameter<N, E>(graph: &Graph<N, E>) -> Option<usize> {
+    eccentricities(graph)?.values().copied().max()
+}
+
+pub fn radius<N, E>(graph: &Graph<N, E>) -> Option<usize> {
+    eccentricities(graph)?.values().copied().min()
+}
+
+pub fn center<N, E>(graph: &Graph<N, E>) -> Vec<u32> {
+    match eccentricities(graph) {
+        None => Vec::new(),
+        Some(ecc) => {
+            let radius = ecc.values().copied().min().unwrap_or(0);
+            ecc.iter()
+                .filter(|(_, e)| **e == radius)
+                .map(|(vert, _)| *vert)
+                .collect()
+        }
+    }
+}
+
+pub fn periphery<N, E>(graph: &Graph<N, E>) -> Vec<u32> {
+    match eccentricities(graph) {
+        None => Vec::new(),
+        Some(ecc) => {
+            let diameter = ecc.values().copied().max().unwrap_or(0);
+            ecc.iter()
+                .filter(|(_, e)| **e == diameter)
+                .map(|(vert, _)| *vert)
+                .collect()
+        }
+    }
+}
+
+pub struct DoubleSweep {
+    endpoints: (u32, u32),
+    diameter_lower_bound: usize,
+    center: u32,
+    radius_upper_bound: usize,
+}
+
+impl DoubleSweep {
+    pub fn endpoints(&self) -> (u32, u32) {
+        self.endpoints
+    }
+
+    pub fn diameter_lower_bound(&self) -> usize {
+        self.diameter_lower_bound
+    }
+
+    pub fn center(&self) -> u32 {
+        self.center
+    }
+
+    pub fn radius_upper_bound(&self) -> usize {
+        self.radius_upper_bound
+    }
+}
+
+pub fn double_sweep<N, E>(graph: &Graph<N, E>, start: u32) -> Option<DoubleSweep> {
+    if !graph.contains_vertex(start) {
+        return Option::None;
+    }
+    let (first_sweep, _) = bfs_tree(graph, start);
+    let (from, _) = farthest(&first_sweep);
+    let (second_sweep, parents) = bfs_tree(graph, from);
+    let (to, length) = farthest(&second_sweep);
+    let mut center = to;
+    for _ in 0..length / 2 {
+        center = parents[&center];
+    }
+    let (from_center, _) = bfs_tree(graph, center);
+    Option::Some(DoubleSweep {
+        endpoints: (from, to),
+        diameter_lower_bound: length,
+        center,
+        radius_upper_bound: farthest(&from_center).1,
+    })
+}
+
+pub fn density<N, E>(graph: &Graph<N, E>) -> f64 {
+    let vertices = graph.adj.len();
+    if vertices < 2 {
+        return 0.0;
+    }
+    let mut pairs = HashSet::new();
+    for (vert, edges) in graph.adj.iter() {
+        for edge in edges.iter().filter(|e| e.to != *vert) {
+            pairs.insert((*vert, edge.to));
+        }
+    }
+    pairs.len() as f64 / (vertices * (vertices - 1)) as f64
+}
+
+pub fn degree_distribution<N, E>(graph: &Graph<N, E>) -> BTreeMap<usize, usize> {
+    let mut result = BTreeMap::new();
+    for edges in graph.adj.values() {
+        *result.entry(edges.len()).or_insert(0) += 1;
+    }
+    result
+}
+
+fn links_between(neighbours: &BTreeMap<u32, BTreeSet<u32>>, vert: u32) -> usize {
+    let adjacent = &neighbours[&vert];
+    adjacent
+        .iter()
+        .map(|other| neighbours[other].intersection(adjacent).count())
+        .sum::<usize>()
+        / 2
+}
+
+pub fn local_clustering<N, E>(graph: &Graph<N, E>) -> BTreeMap<u32, f64> {
+    let neighbours = graph.neighbour_sets();
+    let mut result = BTreeMap::new();
+    for (vert, adjacent) in neighbours.iter() {
+        let degree = adjacent.len();
+        let coefficient = if degree < 2 {
+            0.0
+        } else {
+            let possible = degree * (degree - 1) / 2;
+            links_between(&neighbours, *vert) as f64 / possible as f64
+        };
+        result.insert(*vert, coefficient);
+    }
+    result
+}
+
+pub fn average_clustering<N, E>(graph: &Graph<N, E>) -> f64 {
+    let local = local_clustering(graph);
+    if local.is_empty() {
+        return 0.0;
+    }
+    local.values().sum::<f64>() / local.len() as f64
+}
+
+pub fn global_clustering<N, E>(graph: &Graph<N, E>) -> f64 {
+    let neighbours = graph.neighbour_sets();
+    let mut closed = 0;
+    let mut triples = 0;
+    for (vert, adjacent) in neighbours.iter() {
+        let degree = adjacent.len();
+        if degree >= 2 {
+            triples += degree * (degree - 1) / 2;
+            closed += links_between(&neighbours, *vert);
+        }
+    }
+    if triples == 0 {
+        0.0
+    } else {
+        closed as f64 / triples as f64
+    }
+}
+
+pub fn connected_components<N, E>(graph: &Graph<N, E>) -> Vec<Vec<u32>> {
+    let neighbours = graph.neighbour_sets();
+    let mut visited: HashSet<u32> = HashSet::new();
+    let mut result = Vec::new();
+    for vert in neighbours.keys() {
+        if !visited.insert(*vert) {
+            continue;
+        }
+        let mut component = vec![*vert];
+        let mut stack = vec![*vert];
+        while let Some(curr) = stack.pop() {
+            for next in neighbours[&curr].iter() {
+                if visited.insert(*next) {
+                    component.push(*next);
+                    stack.push(*next);
+                }
+            }
+        }
+        component.sort_unstable();
+        result.push(component);
+    }
+    result
+}
+
+pub fn connected_component_count<N, E>(graph: &Graph<N, E>) -> usize {
+    connected_components(graph).len()
+}
+
+pub fn strongly_connected_component_count<N, E>(graph: &Graph<N, E>) -> usize {
+    strongly_connected_components(graph).len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::undirected;
+    use crate::graph::Edge;
+
+    #[test]
+    fn distance_metrics_on_a_path() {
+        let graph = undirected(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+        assert_eq!(eccentricity(&graph, 1), Option::Some(4));
+        assert_eq!(eccentricity(&graph, 3), Option::Some(2));
+        assert_eq!(eccentricity(&graph, 9), Option::None);
+        assert_eq!(diameter(&graph), Option::Some(4));
+        assert_eq!(radius(&graph), Option::Some(2));
+        assert_eq!(center(&graph), vec![3]);
+        assert_eq!(periphery(&graph), vec![1, 5]);
+        let sweep = double_sweep(&graph, 3).unwrap();
+        assert_eq!(sweep.diameter_lower_bound(), 4);
+        assert_eq!(sweep.center(), 3);
+        assert_eq!(sweep.radius_upper_bound(), 2);
+    }
+
+    #[test]
+    fn disconnected_graphs_have_no_diameter() {
+        let graph = undirected(&[(1, 2), (3, 4), (4, 5)]);
+        assert_eq!(diameter(&graph), Option::None);
+        assert!(center(&graph).is_empty());
+        assert_eq!(
+            connected_components(&graph),
+            vec![vec![1, 2], vec![3, 4, 5]]
+        );
+        assert_eq!(connected_component_count(&graph), 2);
+    }
+
+    #[test]
+    fn clustering_and_density_of_a_triangle_with_a_tail() {
+        let graph = undirected(&[(1, 2), (2, 3), (1, 3), (3, 4)]);
+        let local = local_clustering(&graph);
+        assert_eq!(local[&1], 1.0);
+        assert!((local[&3] - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(local[&4], 0.0);
+        assert!((global_clustering(&graph) - 0.6).abs() < 1e-12);
+        assert!((average_clustering(&graph) - 7.0 / 12.0).abs() < 1e-12);
+        assert!((density(&graph) - 2.0 / 3.0).abs() < 1e-12);
+        let distribution = degree_distribution(&graph);
+        assert_eq!(distribution.get(&1), Option::Some(&1));
+        assert_eq!(distribution.get(&2), Option::Some(&2));
+        assert_eq!(distribution.get(&3), Option::Some(&1));
+    }
+
+    #[test]
+    fn self_loops_do_not_affect_clustering() {
+        let mut graph = undirected(&[(1, 2), (2, 3), (1, 3)]);
+        graph.add_adj(Edge::new(1, 1));
+        assert_eq!(local_clustering(&graph)[&1], 1.0);
+        assert_eq!(global_clustering(&graph), 1.0);
+    }
+
+    #[test]
+    fn directed_components() {
+        let mut graph: Graph = Graph::new();
+        for (from, to) in [(1, 2), (2, 1), (2, 3), (3, 4), (4, 3)].iter() {
+            graph.add_adj(Edge::new(*from, *to));
+        }
+        assert_eq!(connected_component_count(&graph), 1);
+        assert_eq!(strongly_connected_component_count(&graph), 2);
+    }
+}
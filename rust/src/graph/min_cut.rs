@@ -0,0 +1,343 @@
+use crate::graph::Graph;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+
+pub struct MinCut {
+    weight: u64,
+    first: Vec<u32>,
+    second: Vec<u32>,
+}
+
+impl MinCut {
+    fn new(weight: u64, mut first: Vec<u32>, vertices: &[u32]) -> MinCut {
+        first.sort_unstable();
+        let second = vertices
+            .iter()
+            .filter(|v| first.binary_search(v).is_err())
+            .copied()
+            .collect();
+        MinCut {
+            weight,
+            first,
+            second,
+        }
+    }
+
+    pub fn weight(&self) -> u64 {
+        self.weight
+    }
+
+    pub fn first(&self) -> &Vec<u32> {
+        &self.first
+    }
+
+    pub fn second(&self) -> &Vec<u32> {
+        &self.second
+    }
+}
+
+fn weight_maps<N, E>(
+    graph: &Graph<N, E>,
+    vertices: &[u32],
+    weight: &impl Fn(&E) -> u64,
+) -> Vec<BTreeMap<usize, u64>> {
+    let mut index = HashMap::new();
+    for (idx, vert) in vertices.iter().enumerate() {
+        index.insert(*vert, idx);
+    }
+    let mut result = vec![BTreeMap::new(); vertices.len()];
+    for (from, vert) in vertices.iter().enumerate() {
+        for edge in graph.adj.get(vert).unwrap().iter() {
+            let to = *index.get(&edge.to).unwrap();
+            if to != from {
+                *result[from].entry(to).or_insert(0) += weight(&edge.value);
+            }
+        }
+    }
+    result
+}
+
+pub fn min_cut_stoer_wagner(graph: &Graph) -> Option<MinCut> {
+    min_cut_stoer_wagner_by(graph, |value| value.unwrap_or(1) as u64)
+}
+
+pub fn min_cut_stoer_wagner_by<N, E>(
+    graph: &Graph<N, E>,
+    weight: impl Fn(&E) -> u64,
+) -> Option<MinCut> {
+    let vertices = graph.vertices();
+    if vertices.len() < 2 {
+        return Option::None;
+    }
+    let mut weights = weight_maps(graph, &vertices, &weight);
+    let mut groups: Vec<Vec<u32>> = vertices.iter().map(|v| vec![*v]).collect();
+    let mut active: Vec<usize> = (0..vertices.len()).collect();
+    let mut best: Option<(u64, Vec<u32>)> = Option::None;
+    while active.len() > 1 {
+        let mut connectivity = vec![0u64; vertices.len()];
+        let mut added = vec![false; vertices.len()];
+        let mut queue: BinaryHeap<(u64, Reverse<usize>)> =
+            active.iter().map(|vert| (0, Reverse(*vert))).collect();
+        let mut previous = active[0];
+        let mut last = active[0];
+        while let Some((conn, Reverse(vert))) = queue.pop() {
+            if added[vert] || conn != connectivity[vert] {
+                continue;
+            }
+            added[vert] = true;
+            previous = last;
+            last = vert;
+            for (neighbour, weight) in weights[last].iter() {
+                if !added[*neighbour] {
+                    connectivity[*neighbour] += weight;
+                    queue.push((connectivity[*neighbour], Reverse(*neighbour)));
+                }
+            }
+        }
+        let cut_of_phase = connectivity[last];
+        if best
+            .as_ref()
+            .map(|(w, _)| cut_of_phase < *w)
+            .unwrap_or(true)
+        {
+            best = Option::Some((cut_of_phase, groups[last].clone()));
+        }
+        let merged = std::mem::take(&mut weights[last]);
+        for (neighbour, weight) in merged.into_iter() {
+            weights[neighbour].remove(&last);
+            if neighbour != previous {
+                *weights[previous].entry(neighbour).or_insert(0) += weight;
+                *weights[neighbour].entry(previous).or_insert(0) += weight;
+            }
+        }
+        let moved = std::mem::take(&mut groups[last]);
+        groups[previous].extend(moved);
+        active.retain(|v| *v != last);
+    }
+    best.map(|(weight, side)| MinCut::new(weight, side, &vertices))
+}
+
+#[derive(Clone)]
+struct Contraction {
+    weights: Vec<Vec<u64>>,
+    degree: Vec<u64>,
+    groups: Vec<Vec<u32>>,
+    active: Vec<usize>,
+}
+
+impl Contraction {
+    fn new<N, E>(
+        graph: &Graph<N, E>,
+        vertices: &[u32],
+        weight: &impl Fn(&E) -> u64,
+    ) -> Contraction {
+        let mut weights = vec![vec![0; vertices.len()]; vertices.len()];
+        for (from, targets) in weight_maps(graph, vertices, weight).iter().enumerate() {
+            for (to, weight) in targets.iter() {
+                if from != *to {
+                    weights[from][*to] += weight;
+                }
+            }
+        }
+        let degree = weights.iter().map(|row| row.iter().sum()).collect();
+        Contraction {
+            weights,
+            degree,
+            groups: vertices.iter().map(|v| vec![*v]).collect(),
+            active: (0..vertices.len()).collect(),
+        }
+    }
+
+    fn pick(rng: &mut StdRng, candidates: &[(usize, u64)]) -> Option<usize> {
+        let total: u64 = candidates.iter().map(|(_, w)| w).sum();
+        if total == 0 {
+            return Option::None;
+        }
+        let mut point = rng.gen_range(0..total);
+        for (candidate, weight) in candidates.iter() {
+            if point < *weight {
+                return Option::Some(*candidate);
+            }
+            point -= weight;
+        }
+        Option::None
+    }
+
+    fn merge(&mut self, into: usize, from: usize) {
+        let between = self.weights[into][from] + self.weights[from][into];
+        self.degree[into] = self.degree[into] + self.degree[from] - between;
+        self.degree[from] = 0;
+        for other in self.active.iter() {
+            let weight = self.weights[from][*other];
+            self.weights[into][*other] += weight;
+            self.weights[*other][into] += weight;
+            self.weights[from][*other] = 0;
+            self.weights[*other][from] = 0;
+        }
+        self.weights[into][into] = 0;
+        let moved = std::mem::take(&mut self.groups[from]);
+        self.groups[into].extend(moved);
+        self.active.retain(|v| *v != from);
+    }
+
+    fn contract(&mut self, rng: &mut StdRng, target: usize) {
+        while self.active.len() > target {
+            let degrees: Vec<(usize, u64)> =
+                self.active.iter().map(|v| (*v, self.degree[*v])).collect();
+            let first = match Contraction::pick(rng, &degrees) {
+                Some(vert) => vert,
+                None => {
+                    let (into, from) = (self.active[0], self.active[1]);
+                    self.merge(into, from);
+                    continue;
+                }
+            };
+            let adjacent: Vec<(usize, u64)> = self
+                .active
+                .iter()
+                .map(|o| (*o, self.weights[first][*o]))
+                .collect();
+            let second = Contraction::pick(rng, &adjacent).unwrap();
+            self.merge(first, second);
+        }
+    }
+
+    fn cut(&self) -> (u64, Vec<u32>) {
+        let (first, second) = (self.active[0], self.active[1]);
+        (self.weights[first][second], self.groups[first].clone())
+    }
+}
+
+fn karger_stein_recursive(contraction: Contraction, rng: &mut StdRng) -> (u64, Vec<u32>) {
+    let size = contraction.active.len();
+    if size <= 6 {
+        let mut best: Option<(u64, Vec<u32>)> = Option::None;
+        for _ in 0..size * size {
+            let mut attempt = contraction.clone();
+            attempt.contract(rng, 2);
+            let cut = attempt.cut();
+            if best.as_ref().map(|(w, _)| cut.0 < *w).unwrap_or(true) {
+                best = Option::Some(cut);
+            }
+        }
+        return best.unwrap();
+    }
+    let target = (1.0 + size as f64 / std::f64::consts::SQRT_2).ceil() as usize;
+    let mut best: Option<(u64, Vec<u32>)> = Option::None;
+    for _ in 0..2 {
+        let mut attempt = contraction.clone();
+        attempt.contract(rng, target);
+        let cut = karger_stein_recursive(attempt, rng);
+        if best.as_ref().map(|(w, _)| cut.0 < *w).unwrap_or(true) {
+            best = Option::Some(cut);
+        }
+    }
+    best.unwrap()
+}
+
+pub fn min_cut_karger_stein(graph: &Graph, seed: u64, trials: usize) -> Option<MinCut> {
+    min_cut_karger_stein_by(graph, seed, trials, |value| value.unwrap_or(1) as u64)
+}
+
+pub fn min_cut_karger_stein_by<N, E>(
+    graph: &Graph<N, E>,
+    seed: u64,
+    trials: usize,
+    weight: impl Fn(&E) -> u64,
+) -> Option<MinCut> {
+    let vertices = graph.vertices();
+    if vertices.len() < 2 {
+        return Option::None;
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let contraction = Contraction::new(graph, &vertices, &weight);
+    let mut best: Option<(u64, Vec<u32>)> = Option::None;
+    for _ in 0..trials.max(1) {
+        let cut = karger_stein_recursive(contraction.clone(), &mut rng);
+        if best.as_ref().map(|(w, _)| cut.0 < *w).unwrap_or(true) {
+            best = Option::Some(cut);
+        }
+    }
+    best.map(|(weight, side)| MinCut::new(weight, side, &vertices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::undirected_weighted;
+    use crate::graph::Edge;
+
+    fn two_triangles(bridge: u32) -> Graph {
+        undirected_weighted(&[
+            (1, 2, 3),
+            (2, 3, 3),
+            (1, 3, 3),
+            (4, 5, 3),
+            (5, 6, 3),
+            (4, 6, 3),
+            (3, 4, bridge),
+        ])
+    }
+
+    #[test]
+    fn stoer_wagner_finds_the_bridge() {
+        let cut = min_cut_stoer_wagner(&two_triangles(1)).unwrap();
+        assert_eq!(cut.weight(), 1);
+        assert!(cut.first() == &vec![1, 2, 3] || cut.first() == &vec![4, 5, 6]);
+        assert_eq!(cut.first().len() + cut.second().len(), 6);
+    }
+
+    #[test]
+    fn stoer_wagner_handles_weights_beyond_u32() {
+        let mut graph: Graph<(), u64> = Graph::new();
+        graph.add_adj_both(Edge::with_value(3_000_000_000, 1, 2));
+        graph.add_adj_both(Edge::with_value(3_000_000_000, 2, 3));
+        graph.add_adj_both(Edge::with_value(4_000_000_000, 1, 3));
+        let cut = min_cut_stoer_wagner_by(&graph, |value| *value).unwrap();
+        assert_eq!(cut.weight(), 6_000_000_000);
+        assert_eq!(cut.first(), &vec![2]);
+    }
+
+    #[test]
+    fn stoer_wagner_separates_disconnected_graphs_for_free() {
+        let graph = undirected_weighted(&[(1, 2, 4), (3, 4, 5)]);
+        let cut = min_cut_stoer_wagner(&graph).unwrap();
+        assert_eq!(cut.weight(), 0);
+        assert!(min_cut_stoer_wagner(&undirected_weighted(&[])).is_none());
+    }
+
+    #[test]
+    fn contraction_keeps_degrees_in_step_with_the_matrix() {
+        let mut graph = two_triangles(2);
+        graph.add_adj(Edge::new_weighted(5, 1, 6));
+        graph.add_adj(Edge::new_weighted(7, 2, 2));
+        let vertices = graph.vertices();
+        let mut contraction = Contraction::new(&graph, &vertices, &|value: &Option<u32>| {
+            value.unwrap_or(1) as u64
+        });
+        let mut rng = StdRng::seed_from_u64(33);
+        for target in (2..vertices.len()).rev() {
+            contraction.contract(&mut rng, target);
+            for vert in contraction.active.iter() {
+                let row: u64 = contraction
+                    .active
+                    .iter()
+                    .map(|other| contraction.weights[*vert][*other])
+                    .sum();
+                assert_eq!(contraction.degree[*vert], row);
+            }
+        }
+    }
+
+    #[test]
+    fn karger_stein_agrees_with_stoer_wagner() {
+        for bridge in 1..=4 {
+            let graph = two_triangles(bridge);
+            let exact = min_cut_stoer_wagner(&graph).unwrap();
+            let randomized = min_cut_karger_stein(&graph, 7, 10).unwrap();
+            assert_eq!(randomized.weight(), exact.weight());
+        }
+    }
+}
@@ -0,0 +1,408 @@
+use crate::graph::{Edge, Edges, Graph};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+fn empty_like<N, E>(graph: &Graph<N, E>) -> Graph<N, E> {
+    let mut result = Graph::with_policy(graph.policy);
+    result.next_edge_id = graph.next_edge_id;
+    result
+}
+
+fn copy_vertex<N: Clone, E>(result: &mut Graph<N, E>, graph: &Graph<N, E>, vert: u32) {
+    result.adj.entry(vert).or_insert(Edges::Empty);
+    if let Some(payload) = graph.payloads.get(&vert) {
+        result.payloads.insert(vert, payload.clone());
+    }
+}
+
+fn logical_edges<N, E>(graph: &Graph<N, E>) -> Vec<(&Edge<E>, bool)> {
+    let mut seen = HashSet::new();
+    let mut result = Vec::new();
+    for edge in graph.adj.values().flat_map(|edges| edges.iter()) {
+        if edge.id.is_some_and(|id| !seen.insert(id)) {
+            continue;
+        }
+        let both = edge.id.is_some_and(|id| graph.is_undirected(id));
+        result.push((edge, both));
+    }
+    result
+}
+
+pub fn induced_subgraph<N: Clone, E: Clone>(graph: &Graph<N, E>, vertices: &[u32]) -> Graph<N, E> {
+    let keep: HashSet<u32> = vertices
+        .iter()
+        .copied()
+        .filter(|v| graph.contains_vertex(*v))
+        .collect();
+    let mut result = empty_like(graph);
+    for vert in graph.adj.keys().filter(|v| keep.contains(v)) {
+        copy_vertex(&mut result, graph, *vert);
+    }
+    for (edge, both) in logical_edges(graph) {
+        if keep.contains(&edge.from) && keep.contains(&edge.to) {
+            result.link_edge(edge.clone(), both, edge.id);
+        }
+    }
+    result
+}
+
+pub fn edge_subgraph<N: Clone, E: Clone>(
+    graph: &Graph<N, E>,
+    predicate: impl Fn(&Edge<E>) -> bool,
+) -> Graph<N, E> {
+    let mut result = empty_like(graph);
+    for vert in graph.adj.keys() {
+        copy_vertex(&mut result, graph, *vert);
+    }
+    for (edge, both) in logical_edges(graph) {
+        if predicate(edge) {
+            result.link_edge(edge.clone(), both, edge.id);
+        }
+    }
+    result
+}
+
+pub fn reverse<N: Clone, E: Clone>(graph: &Graph<N, E>) -> Graph<N, E> {
+    let mut result = empty_like(graph);
+    for vert in graph.adj.keys() {
+        copy_vertex(&mut result, graph, *vert);
+    }
+    for (edge, both) in logical_edges(graph) {
+        result.link_edge(edge.reverse(), both, edge.id);
+    }
+    result
+}
+
+pub fn complement<N: Clone, E: Clone>(graph: &Graph<N, E>, value: E) -> Graph<N, E> {
+    let mut result = Graph::with_policy(graph.policy);
+    for vert in graph.adj.keys() {
+        copy_vertex(&mut result, graph, *vert);
+    }
+    let both = graph.is_undirected_graph();
+    for from in graph.adj.keys() {
+        let adjacent: HashSet<u32> = graph.edges_from(*from).map(|e| e.to).collect();
+        for to in graph.adj.keys() {
+            if from == to || adjacent.contains(to) || (both && to < from) {
+                continue;
+            }
+            let edge = Edge::with_value(value.clone(), *from, *to);
+            result.link_edge(edge, both, Option::None);
+        }
+    }
+    result
+}
+
+fn value_counts<N, E: PartialEq>(graph: &Graph<N, E>, from: u32, to: u32) -> Vec<(&E, usize)> {
+    let mut result: Vec<(&E, usize)> = Vec::new();
+    for edge in graph.edges_from(from).filter(|e| e.to == to) {
+        match result.iter_mut().find(|(value, _)| **value == edge.value) {
+            Some((_, count)) => *count += 1,
+            None => result.push((&edge.value, 1)),
+        }
+    }
+    result
+}
+
+fn take_match<'a, N, E: PartialEq>(
+    remaining: &mut HashMap<(u32, u32), Vec<(&'a E, usize)>>,
+    other: &'a Graph<N, E>,
+    edge: &Edge<E>,
+) -> bool {
+    let counts = remaining
+        .entry((edge.from, edge.to))
+        .or_insert_with(|| value_counts(other, edge.from, edge.to));
+    match counts
+        .iter_mut()
+        .find(|(value, count)| **value == edge.value && *count > 0)
+    {
+        Some((_, count)) => {
+            *count -= 1;
+            true
+        }
+        None => false,
+    }
+}
+
+pub fn union<N: Clone, E: Clone + PartialEq>(
+    first: &Graph<N, E>,
+    second: &Graph<N, E>,
+) -> Graph<N, E> {
+    let mut result = empty_like(first);
+    for vert in second.adj.keys() {
+        copy_vertex(&mut result, second, *vert);
+    }
+    for vert in first.adj.keys() {
+        copy_vertex(&mut result, first, *vert);
+    }
+    for (edge, both) in logical_edges(first) {
+        result.link_edge(edge.clone(), both, edge.id);
+    }
+    let mut remaining = HashMap::new();
+    for (edge, both) in logical_edges(second) {
+        if !take_match(&mut remaining, first, edge) {
+            result.link_edge(edge.clone(), both, Option::None);
+        }
+    }
+    result
+}
+
+pub fn intersection<N: Clone, E: Clone + PartialEq>(
+    first: &Graph<N, E>,
+    second: &Graph<N, E>,
+) -> Graph<N, E> {
+    let mut result = empty_like(first);
+    for vert in first.adj.keys().filter(|v| second.contains_vertex(**v)) {
+        copy_vertex(&mut result, first, *vert);
+    }
+    let mut remaining = HashMap::new();
+    for (edge, both) in logical_edges(first) {
+        if take_match(&mut remaining, second, edge) {
+            result.link_edge(edge.clone(), both, edge.id);
+        }
+    }
+    result
+}
+
+pub struct GraphDiff<E = Option<u32>> {
+    added_vertices: Vec<u32>,
+    removed_vertices: Vec<u32>,
+    added: Vec<Edge<E>>,
+    removed: Vec<Edge<E>>,
+    reweighted: Vec<(Edge<E>, Edge<E>)>,
+}
+
+impl<E> GraphDiff<E> {
+    pub fn added_vertices(&self) -> &Vec<u32> {
+        &self.added_vertices
+    }
+
+    pub fn removed_vertices(&self) -> &Vec<u32> {
+        &self.removed_vertices
+    }
+
+    pub fn added(&self) -> &Vec<Edge<E>> {
+        &self.added
+    }
+
+    pub fn removed(&self) -> &Vec<Edge<E>> {
+        &self.removed
+    }
+
+    pub fn reweighted(&self) -> &Vec<(Edge<E>, Edge<E>)> {
+        &self.reweighted
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_vertices.is_empty()
+            && self.removed_vertices.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.reweighted.is_empty()
+    }
+}
+
+fn edges_by_target<N, E>(graph: &Graph<N, E>, from: u32) -> BTreeMap<u32, Vec<&Edge<E>>> {
+    let mut result: BTreeMap<u32, Vec<&Edge<E>>> = BTreeMap::new();
+    for edge in graph.edges_from(from) {
+        result.entry(edge.to).or_default().push(edge);
+    }
+    result
+}
+
+pub fn diff<N, E: Clone + PartialEq>(old: &Graph<N, E>, new: &Graph<N, E>) -> GraphDiff<E> {
+    let mut result = GraphDiff {
+        added_vertices: new
+            .adj
+            .keys()
+            .filter(|v| !old.contains_vertex(**v))
+            .copied()
+            .collect(),
+        removed_vertices: old
+            .adj
+            .keys()
+            .filter(|v| !new.contains_vertex(**v))
+            .copied()
+            .collect(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        reweighted: Vec::new(),
+    };
+    let mut sources: Vec<u32> = old.adj.keys().chain(new.adj.keys()).copied().collect();
+    sources.sort_unstable();
+    sources.dedup();
+    for from in sources {
+        let mut before = edges_by_target(old, from);
+        let mut after = edges_by_target(new, from);
+        let mut targets: Vec<u32> = before.keys().chain(after.keys()).copied().collect();
+        targets.sort_unstable();
+        targets.dedup();
+        for to in targets {
+            let mut old_edges = before.remove(&to).unwrap_or_default();
+            let mut new_edges = after.remove(&to).unwrap_or_default();
+            old_edges.retain(|old_edge| {
+                match new_edges.iter().position(|e| e.value == old_edge.value) {
+                    Some(pos) => {
+                        new_edges.remove(pos);
+                        false
+                    }
+                    None => true,
+                }
+            });
+            let paired = old_edges.len().min(new_edges.len());
+            for (old_edge, new_edge) in old_edges.iter().zip(new_edges.iter()) {
+                result
+                    .reweighted
+                    .push(((*old_edge).clone(), (*new_edge).clone()));
+            }
+            result
+                .removed
+                .extend(old_edges.iter().skip(paired).map(|e| (*e).clone()));
+            result
+                .added
+                .extend(new_edges.iter().skip(paired).map(|e| (*e).clone()));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::{directed_weighted, undirected};
+    use crate::graph::ParallelEdgePolicy;
+
+    fn edge_list<N, E: Clone>(graph: &Graph<N, E>) -> Vec<(u32, u32, E)> {
+        graph
+            .adj
+            .values()
+            .flat_map(|edges| edges.iter())
+            .map(|edge| (edge.from, edge.to, edge.value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn induced_subgraph_keeps_payloads_and_inner_edges() {
+        let mut graph: Graph<&str, u32> = Graph::new();
+        graph.insert_vertex(1, "a");
+        graph.insert_vertex(2, "b");
+        graph.add_adj_both(Edge::with_value(4, 1, 2));
+        graph.add_adj_both(Edge::with_value(6, 2, 3));
+        graph.add_adj(Edge::with_value(8, 3, 1));
+        let mut subgraph = induced_subgraph(&graph, &[1, 2, 9]);
+        assert_eq!(subgraph.vertices(), vec![1, 2]);
+        assert_eq!(subgraph.vertex(1), Option::Some(&"a"));
+        assert_eq!(edge_list(&subgraph), vec![(1, 2, 4), (2, 1, 4)]);
+        assert_eq!(subgraph.edge_count(), 1);
+        let id = subgraph.edges_from(1).next().unwrap().id().unwrap();
+        subgraph.set_edge_value(id, 5);
+        assert_eq!(subgraph.edge(2, 1), Option::Some(&5));
+        let id = subgraph.add_edge(2, 2, 1).unwrap();
+        assert!(graph.edge_by_id(id).is_none());
+    }
+
+    #[test]
+    fn edge_subgraph_and_reverse() {
+        let graph = directed_weighted(&[(1, 2, 1), (2, 3, 5), (3, 1, 2)]);
+        let light = edge_subgraph(&graph, |edge| edge.value().unwrap_or(1) < 3);
+        assert_eq!(light.vertices(), vec![1, 2, 3]);
+        assert_eq!(
+            edge_list(&light),
+            vec![(1, 2, Option::Some(1)), (3, 1, Option::Some(2))]
+        );
+        let reversed = reverse(&graph);
+        assert_eq!(
+            edge_list(&reversed),
+            vec![
+                (1, 3, Option::Some(2)),
+                (2, 1, Option::Some(1)),
+                (3, 2, Option::Some(5))
+            ]
+        );
+    }
+
+    #[test]
+    fn complement_fills_missing_pairs() {
+        let graph = directed_weighted(&[(1, 2, 1), (2, 3, 1), (3, 3, 1)]);
+        let complement = complement(&graph, Option::Some(7));
+        let pairs: Vec<(u32, u32)> = edge_list(&complement)
+            .into_iter()
+            .map(|(from, to, _)| (from, to))
+            .collect();
+        assert_eq!(pairs, vec![(1, 3), (2, 1), (3, 1), (3, 2)]);
+        assert_eq!(complement.edge(2, 1), Option::Some(&Option::Some(7)));
+    }
+
+    #[test]
+    fn union_and_intersection_use_multiset_semantics() {
+        let first = directed_weighted(&[(1, 2, 1), (1, 2, 1), (2, 3, 4)]);
+        let second = directed_weighted(&[(1, 2, 1), (2, 3, 5), (3, 4, 1)]);
+        let union = union(&first, &second);
+        assert_eq!(union.vertices(), vec![1, 2, 3, 4]);
+        assert_eq!(union.edges_between(1, 2).len(), 2);
+        assert_eq!(union.edges_between(2, 3).len(), 2);
+        assert_eq!(union.edge_count(), 5);
+        let common = intersection(&first, &second);
+        assert_eq!(common.vertices(), vec![1, 2, 3]);
+        assert_eq!(edge_list(&common), vec![(1, 2, Option::Some(1))]);
+    }
+
+    #[test]
+    fn results_respect_the_parallel_edge_policy() {
+        let mut first: Graph = Graph::with_policy(ParallelEdgePolicy::keep_minimum());
+        first.add_adj(Edge::new_weighted(5, 1, 2));
+        let mut second: Graph = Graph::with_policy(ParallelEdgePolicy::keep_minimum());
+        second.add_adj(Edge::new_weighted(3, 1, 2));
+        let merged = union(&first, &second);
+        assert_eq!(merged.edges_between(1, 2).len(), 1);
+        assert_eq!(merged.edge(1, 2), Option::Some(&Option::Some(3)));
+        let mut first: Graph = Graph::with_policy(ParallelEdgePolicy::RejectDuplicates);
+        first.add_adj(Edge::new_weighted(5, 1, 2));
+        let mut second: Graph = Graph::new();
+        second.add_adj(Edge::new_weighted(3, 1, 2));
+        second.add_adj(Edge::new_weighted(4, 1, 2));
+        let merged = union(&first, &second);
+        assert_eq!(merged.edge_count(), 1);
+        assert_eq!(merged.edge(1, 2), Option::Some(&Option::Some(5)));
+    }
+
+    #[test]
+    fn undirected_inputs_stay_undirected() {
+        let mut graph = undirected(&[(1, 2), (2, 3)]);
+        graph.add_vertex(4);
+        let complement = complement(&graph, Option::None);
+        assert!(complement.is_undirected_graph());
+        assert_eq!(complement.edge_count(), 4);
+        assert!(complement.edge(3, 1).is_some() && complement.edge(1, 3).is_some());
+        let other = undirected(&[(2, 3), (3, 4)]);
+        let merged = union(&graph, &other);
+        assert!(merged.is_undirected_graph());
+        assert_eq!(merged.edge_count(), 3);
+        let common = intersection(&graph, &other);
+        assert!(common.is_undirected_graph());
+        assert_eq!(common.edge_count(), 1);
+        let id = common.edges_from(3).next().unwrap().id().unwrap();
+        assert!(graph.edge_by_id(id).is_some());
+        let subgraph = induced_subgraph(&graph, &[2, 3]);
+        assert!(subgraph.is_undirected_graph());
+        assert_eq!(subgraph.edge_count(), 1);
+        assert_eq!(reverse(&graph).edge_count(), 2);
+    }
+
+    #[test]
+    fn diff_reports_every_change() {
+        let old = directed_weighted(&[(1, 2, 1), (2, 3, 4), (3, 4, 1)]);
+        let new = directed_weighted(&[(1, 2, 1), (2, 3, 6), (1, 5, 2)]);
+        let changes = diff(&old, &new);
+        assert_eq!(changes.added_vertices(), &vec![5]);
+        assert_eq!(changes.removed_vertices(), &vec![4]);
+        assert_eq!(changes.added().len(), 1);
+        assert_eq!(changes.added()[0].to(), 5);
+        assert_eq!(changes.removed().len(), 1);
+        assert_eq!(changes.removed()[0].from(), 3);
+        assert_eq!(changes.reweighted().len(), 1);
+        let (before, after) = &changes.reweighted()[0];
+        assert_eq!(before.value(), &Option::Some(4));
+        assert_eq!(after.value(), &Option::Some(6));
+        assert!(!changes.is_empty());
+        assert!(diff(&old, &old).is_empty());
+    }
+}
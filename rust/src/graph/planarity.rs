@@ -0,0 +1,816 @@
+use crate::graph::Graph;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+#[derive(Clone, Copy, Default)]
+struct Interval {
+    low: Option<usize>,
+    high: Option<usize>,
+}
+
+impl Interval {
+    fn is_empty(&self) -> bool {
+        self.low.is_none() && self.high.is_none()
+    }
+
+    fn conflicting(&self, edge: usize, lowpt: &[usize]) -> bool {
+        self.high.is_some_and(|high| lowpt[high] > lowpt[edge])
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct ConflictPair {
+    left: Interval,
+    right: Interval,
+}
+
+impl ConflictPair {
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.left, &mut self.right);
+    }
+
+    fn lowest(&self, lowpt: &[usize]) -> Option<usize> {
+        let left = self.left.low.map(|low| lowpt[low]);
+        let right = self.right.low.map(|low| lowpt[low]);
+        match (left, right) {
+            (Some(left), Some(right)) => Option::Some(left.min(right)),
+            (left, None) => left,
+            (None, right) => right,
+        }
+    }
+}
+
+struct Rotation {
+    cw: Vec<HashMap<usize, usize>>,
+    ccw: Vec<HashMap<usize, usize>>,
+    first: Vec<Option<usize>>,
+}
+
+impl Rotation {
+    fn add_cw(&mut self, start: usize, end: usize, reference: Option<usize>) {
+        match reference {
+            None => {
+                self.cw[start].insert(end, end);
+                self.ccw[start].insert(end, end);
+                self.first[start] = Option::Some(end);
+            }
+            Some(reference) => {
+                let after = self.cw[start][&reference];
+                self.cw[start].insert(reference, end);
+                self.cw[start].insert(end, after);
+                self.ccw[start].insert(after, end);
+                self.ccw[start].insert(end, reference);
+            }
+        }
+    }
+
+    fn add_ccw(&mut self, start: usize, end: usize, reference: usize) {
+        let before = self.ccw[start][&reference];
+        self.add_cw(start, end, Option::Some(before));
+        if self.first[start] == Option::Some(reference) {
+            self.first[start] = Option::Some(end);
+        }
+    }
+
+    fn add_first(&mut self, start: usize, end: usize) {
+        match self.first[start] {
+            None => self.add_cw(start, end, Option::None),
+            Some(first) => self.add_ccw(start, end, first),
+        }
+    }
+
+    fn clockwise(&self, vert: usize) -> Vec<usize> {
+        let mut result = Vec::new();
+        if let Some(first) = self.first[vert] {
+            let mut curr = first;
+            loop {
+                result.push(curr);
+                curr = self.cw[vert][&curr];
+                if curr == first {
+                    break;
+                }
+            }
+        }
+        result
+    }
+}
+
+struct LeftRight {
+    adjacency: Vec<Vec<usize>>,
+    height: Vec<Option<usize>>,
+    roots: Vec<usize>,
+    oriented: HashSet<(usize, usize)>,
+    from: Vec<usize>,
+    to: Vec<usize>,
+    out: Vec<Vec<usize>>,
+    lowpt: Vec<usize>,
+    lowpt2: Vec<usize>,
+    nesting_depth: Vec<i64>,
+    parent_edge: Vec<Option<usize>>,
+    ordered: Vec<Vec<usize>>,
+    reference: Vec<Option<usize>>,
+    side: Vec<i64>,
+    stack: Vec<ConflictPair>,
+    stack_bottom: Vec<usize>,
+    lowpt_edge: Vec<usize>,
+    left_ref: Vec<usize>,
+    right_ref: Vec<usize>,
+}
+
+impl LeftRight {
+    fn new(size: usize, edges: &[(usize, usize)]) -> LeftRight {
+        let mut adjacency = vec![Vec::new(); size];
+        for (first, second) in edges.iter() {
+            adjacency[*first].push(*second);
+            adjacency[*second].push(*first);
+        }
+        LeftRight {
+            adjacency,
+            height: vec![Option::None; size],
+            roots: Vec::new(),
+            oriented: HashSet::new(),
+            from: Vec::new(),
+            to: Vec::new(),
+            out: vec![Vec::new(); size],
+            lowpt: Vec::new(),
+            lowpt2: Vec::new(),
+            nesting_depth: Vec::new(),
+            parent_edge: vec![Option::None; size],
+            ordered: Vec::new(),
+            reference: Vec::new(),
+            side: Vec::new(),
+            stack: Vec::new(),
+            stack_bottom: Vec::new(),
+            lowpt_edge: Vec::new(),
+            left_ref: vec![0; size],
+            right_ref: vec![0; size],
+        }
+    }
+
+    fn height(&self, vert: usize) -> usize {
+        self.height[vert].unwrap()
+    }
+
+    fn dfs_orientation(&mut self, root: usize) {
+        let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+        while let Some((vert, position)) = stack.last_mut() {
+            let vert = *vert;
+            if let Some(next) = self.adjacency[vert].get(*position).copied() {
+                *position += 1;
+                if !self.oriented.insert((vert.min(next), vert.max(next))) {
+                    continue;
+                }
+                let edge = self.from.len();
+                self.from.push(vert);
+                self.to.push(next);
+                self.out[vert].push(edge);
+                self.lowpt.push(self.height(vert));
+                self.lowpt2.push(self.height(vert));
+                self.nesting_depth.push(0);
+                match self.height[next] {
+                    None => {
+                        self.parent_edge[next] = Option::Some(edge);
+                        self.height[next] = Option::Some(self.height(vert) + 1);
+                        stack.push((next, 0));
+                    }
+                    Some(height) => {
+                        self.lowpt[edge] = height;
+                        self.finish_orientation(edge);
+                    }
+                }
+            } else {
+                stack.pop();
+                if let Some(edge) = self.parent_edge[vert] {
+                    self.finish_orientation(edge);
+                }
+            }
+        }
+    }
+
+    fn finish_orientation(&mut self, edge: usize) {
+        let vert = self.from[edge];
+        self.nesting_depth[edge] = 2 * self.lowpt[edge] as i64;
+        if self.lowpt2[edge] < self.height(vert) {
+            self.nesting_depth[edge] += 1;
+        }
+        if let Some(parent) = self.parent_edge[vert] {
+            if self.lowpt[edge] < self.lowpt[parent] {
+                self.lowpt2[parent] = self.lowpt[parent].min(self.lowpt2[edge]);
+                self.lowpt[parent] = self.lowpt[edge];
+            } else if self.lowpt[edge] > self.lowpt[parent] {
+                self.lowpt2[parent] = self.lowpt2[parent].min(self.lowpt[edge]);
+            } else {
+                self.lowpt2[parent] = self.lowpt2[parent].min(self.lowpt2[edge]);
+            }
+        }
+    }
+
+    fn dfs_testing(&mut self, root: usize) -> bool {
+        let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+        while let Some((vert, position)) = stack.last_mut() {
+            let vert = *vert;
+            if let Some(edge) = self.ordered[vert].get(*position).copied() {
+                *position += 1;
+                let idx = *position - 1;
+                let next = self.to[edge];
+                self.stack_bottom[edge] = self.stack.len();
+                if self.parent_edge[next] == Option::Some(edge) {
+                    stack.push((next, 0));
+                    continue;
+                }
+                self.lowpt_edge[edge] = edge;
+                self.stack.push(ConflictPair {
+                    left: Interval::default(),
+                    right: Interval {
+                        low: Option::Some(edge),
+                        high: Option::Some(edge),
+                    },
+                });
+                if !self.finish_testing(edge, idx) {
+                    return false;
+                }
+            } else {
+                stack.pop();
+                if let Some(edge) = self.parent_edge[vert] {
+                    self.remove_back_edges(edge);
+                    let idx = stack.last().unwrap().1 - 1;
+                    if !self.finish_testing(edge, idx) {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn finish_testing(&mut self, edge: usize, idx: usize) -> bool {
+        let vert = self.from[edge];
+        if self.lowpt[edge] < self.height(vert) {
+            let parent = self.parent_edge[vert].unwrap();
+            if idx == 0 {
+                self.lowpt_edge[parent] = self.lowpt_edge[edge];
+            } else if !self.add_constraints(edge, parent) {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn add_constraints(&mut self, edge: usize, parent: usize) -> bool {
+        let mut pair = ConflictPair::default();
+        loop {
+            let mut popped = self.stack.pop().unwrap();
+            if !popped.left.is_empty() {
+                popped.swap();
+            }
+            if !popped.left.is_empty() {
+                return false;
+            }
+            let low = popped.right.low.unwrap();
+            if self.lowpt[low] > self.lowpt[parent] {
+                match pair.right.low {
+                    None => pair.right = popped.right,
+                    Some(pair_low) => self.reference[pair_low] = popped.right.high,
+                }
+                pair.right.low = popped.right.low;
+            } else {
+                self.reference[low] = Option::Some(self.lowpt_edge[parent]);
+            }
+            if self.stack.len() == self.stack_bottom[edge] {
+                break;
+            }
+        }
+        while let Some(top) = self.stack.last() {
+            if !top.left.conflicting(edge, &self.lowpt) && !top.right.conflicting(edge, &self.lowpt)
+            {
+                break;
+            }
+            let mut popped = self.stack.pop().unwrap();
+            if popped.right.conflicting(edge, &self.lowpt) {
+                popped.swap();
+            }
+            if popped.right.conflicting(edge, &self.lowpt) {
+                return false;
+            }
+            if let Some(low) = pair.right.low {
+                self.reference[low] = popped.right.high;
+            }
+            if popped.right.low.is_some() {
+                pair.right.low = popped.right.low;
+            }
+            match pair.left.low {
+                None => pair.left = popped.left,
+                Some(low) => self.reference[low] = popped.left.high,
+            }
+            pair.left.low = popped.left.low;
+        }
+        if !pair.left.is_empty() || !pair.right.is_empty() {
+            self.stack.push(pair);
+        }
+        true
+    }
+
+    fn remove_back_edges(&mut self, edge: usize) {
+        let vert = self.from[edge];
+        let height = self.height(vert);
+        while let Some(top) = self.stack.last() {
+            if top.lowest(&self.lowpt) != Option::Some(height) {
+                break;
+            }
+            let popped = self.stack.pop().unwrap();
+            if let Some(low) = popped.left.low {
+                self.side[low] = -1;
+            }
+        }
+        if let Some(mut pair) = self.stack.pop() {
+            while let Some(high) = pair.left.high {
+                if self.to[high] != vert {
+                    break;
+                }
+                pair.left.high = self.reference[high];
+            }
+            if pair.left.high.is_none() {
+                if let Some(low) = pair.left.low {
+                    self.reference[low] = pair.right.low;
+                    self.side[low] = -1;
+                    pair.left.low = Option::None;
+                }
+            }
+            while let Some(high) = pair.right.high {
+                if self.to[high] != vert {
+                    break;
+                }
+                pair.right.high = self.reference[high];
+            }
+            if pair.right.high.is_none() {
+                if let Some(low) = pair.right.low {
+                    self.reference[low] = pair.left.low;
+                    self.side[low] = -1;
+                    pair.right.low = Option::None;
+                }
+            }
+            self.stack.push(pair);
+        }
+        if self.lowpt[edge] < height {
+            let top = self.stack.last().unwrap();
+            self.reference[edge] = match (top.left.high, top.right.high) {
+                (Some(left), Some(right)) if self.lowpt[left] > self.lowpt[right] => {
+                    Option::Some(left)
+                }
+                (Some(left), None) => Option::Some(left),
+                (_, right) => right,
+            };
+        }
+    }
+
+    fn sign(&mut self, edge: usize) -> i64 {
+        let mut chain = Vec::new();
+        let mut curr = edge;
+        while let Some(next) = self.reference[curr] {
+            chain.push(curr);
+            curr = next;
+        }
+        let mut sign = self.side[curr];
+        for link in chain.into_iter().rev() {
+            self.side[link] *= sign;
+            self.reference[link] = Option::None;
+            sign = self.side[link];
+        }
+        self.side[edge]
+    }
+
+    fn sort_by_nesting_depth(&mut self) {
+        let depth = &self.nesting_depth;
+        self.ordered = self.out.clone();
+        for edges in self.ordered.iter_mut() {
+            edges.sort_by_key(|edge| depth[*edge]);
+        }
+    }
+
+    fn dfs_embedding(&mut self, rotation: &mut Rotation, root: usize) {
+        let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+        while let Some((vert, position)) = stack.last_mut() {
+            let vert = *vert;
+            let edge = match self.ordered[vert].get(*position) {
+                None => {
+                    stack.pop();
+                    continue;
+                }
+                Some(edge) => *edge,
+            };
+            *position += 1;
+            let next = self.to[edge];
+            if self.parent_edge[next] == Option::Some(edge) {
+                rotation.add_first(next, vert);
+                self.left_ref[vert] = next;
+                self.right_ref[vert] = next;
+                stack.push((next, 0));
+            } else if self.side[edge] == 1 {
+                rotation.add_cw(next, vert, Option::Some(self.right_ref[next]));
+            } else {
+                rotation.add_ccw(next, vert, self.left_ref[next]);
+                self.left_ref[next] = vert;
+            }
+        }
+    }
+
+    fn run(mut self) -> Option<Vec<Vec<usize>>> {
+        let size = self.adjacency.len();
+        let edges = self.adjacency.iter().map(|a| a.len()).sum::<usize>() / 2;
+        if size > 2 && edges > 3 * size - 6 {
+            return Option::None;
+        }
+        for vert in 0..size {
+            if self.height[vert].is_none() {
+                self.height[vert] = Option::Some(0);
+                self.roots.push(vert);
+                self.dfs_orientation(vert);
+            }
+        }
+        let oriented = self.from.len();
+        self.reference = vec![Option::None; oriented];
+        self.side = vec![1; oriented];
+        self.stack_bottom = vec![0; oriented];
+        self.lowpt_edge = vec![0; oriented];
+        self.sort_by_nesting_depth();
+        for root in self.roots.clone() {
+            if !self.dfs_testing(root) {
+                return Option::None;
+            }
+        }
+        for edge in 0..oriented {
+            self.nesting_depth[edge] *= self.sign(edge);
+        }
+        self.sort_by_nesting_depth();
+        let mut rotation = Rotation {
+            cw: vec![HashMap::new(); size],
+            ccw: vec![HashMap::new(); size],
+            first: vec![Option::None; size],
+        };
+        for vert in 0..size {
+            let mut previous = Option::None;
+            for edge in self.ordered[vert].iter() {
+                rotation.add_cw(vert, self.to[*edge], previous);
+                previous = Option::Some(self.to[*edge]);
+            }
+        }
+        for root in self.roots.clone() {
+            self.dfs_embedding(&mut rotation, root);
+        }
+        Option::Some((0..size).map(|vert| rotation.clockwise(vert)).collect())
+    }
+}
+
+fn simple_edges<N, E>(graph: &Graph<N, E>, index: &HashMap<u32, usize>) -> Vec<(usize, usize)> {
+    let mut pairs = BTreeSet::new();
+    for (vert, edges) in graph.adj.iter() {
+        for edge in edges.iter().filter(|e| e.to != *vert) {
+            let (first, second) = (index[vert], index[&edge.to]);
+            pairs.insert((first.min(second), first.max(second)));
+        }
+    }
+    pairs.into_iter().collect()
+}
+
+pub struct PlanarEmbedding {
+    rotation: BTreeMap<u32, Vec<u32>>,
+}
+
+impl PlanarEmbedding {
+    pub fn neighbours_cw(&self, vert: u32) -> &[u32] {
+        match self.rotation.get(&vert) {
+            None => &[],
+            Some(neighbours) => neighbours.as_slice(),
+        }
+    }
+
+    pub fn rotation_system(&self) -> &BTreeMap<u32, Vec<u32>> {
+        &self.rotation
+    }
+
+    pub fn faces(&self) -> Vec<Vec<u32>> {
+        let mut position: HashMap<(u32, u32), usize> = HashMap::new();
+        for (vert, neighbours) in self.rotation.iter() {
+            for (idx, next) in neighbours.iter().enumerate() {
+                position.insert((*vert, *next), idx);
+            }
+        }
+        let mut visited: HashSet<(u32, u32)> = HashSet::new();
+        let mut result = Vec::new();
+        for (vert, neighbours) in self.rotation.iter() {
+            for next in neighbours.iter() {
+                if visited.contains(&(*vert, *next)) {
+                    continue;
+                }
+                let mut face = Vec::new();
+                let (mut from, mut to) = (*vert, *next);
+                while visited.insert((from, to)) {
+                    face.push(from);
+                    let around = &self.rotation[&to];
+                    let idx = position[&(to, from)];
+                    let following = around[(idx + around.len() - 1) % around.len()];
+                    from = to;
+                    to = following;
+                }
+                result.push(face);
+            }
+        }
+        result
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum KuratowskiKind {
+    K5,
+    K33,
+}
+
+pub struct KuratowskiWitness {
+    edges: Vec<(u32, u32)>,
+    kind: KuratowskiKind,
+}
+
+impl KuratowskiWitness {
+    pub fn edges(&self) -> &Vec<(u32, u32)> {
+        &self.edges
+    }
+
+    pub fn kind(&self) -> KuratowskiKind {
+        self.kind
+    }
+
+    pub fn branch_vertices(&self) -> Vec<u32> {
+        let mut degree: BTreeMap<u32, usize> = BTreeMap::new();
+        for (first, second) in self.edges.iter() {
+            *degree.entry(*first).or_insert(0) += 1;
+            *degree.entry(*second).or_insert(0) += 1;
+        }
+        degree
+            .into_iter()
+            .filter(|(_, d)| *d > 2)
+            .map(|(vert, _)| vert)
+            .collect()
+    }
+}
+
+pub enum Planarity {
+    Planar(PlanarEmbedding),
+    NonPlanar(KuratowskiWitness),
+}
+
+/// Shrinks a non-planar edge set to a minimal non-planar subgraph by deleting
+/// edges that are not needed, first in halving blocks and then one at a time.
+/// Every attempt reruns the O(n) left-right test, so this takes O(k log m) runs
+/// for a witness of k edges and O(m) runs in the worst case.
+fn kuratowski_subgraph(size: usize, edges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut kept: Vec<(usize, usize)> = edges.to_vec();
+    let mut block = kept.len().div_ceil(2).max(1);
+    loop {
+        let mut idx = 0;
+        while idx < kept.len() {
+            let end = kept.len().min(idx + block);
+            let mut candidate = kept[..idx].to_vec();
+            candidate.extend_from_slice(&kept[end..]);
+            if LeftRight::new(size, &candidate).run().is_none() {
+                kept = candidate;
+            } else {
+                idx = end;
+            }
+        }
+        if block == 1 {
+            return kept;
+        }
+        block = block.div_ceil(2);
+    }
+}
+
+fn indexed_edges<N, E>(graph: &Graph<N, E>) -> (Vec<u32>, Vec<(usize, usize)>) {
+    let vertices = graph.vertices();
+    let index: HashMap<u32, usize> = vertices
+        .iter()
+        .enumerate()
+        .map(|(idx, vert)| (*vert, idx))
+        .collect();
+    let edges = simple_edges(graph, &index);
+    (vertices, edges)
+}
+
+/// Runs the left-right planarity test on the underlying simple undirected
+/// graph: edge directions, self-loops and parallel edges are ignored. The test
+/// itself is linear, but a non-planar result also builds a Kuratowski witness,
+/// which costs O(m·(n + m)) in the worst case; `is_planar` skips the witness.
+pub fn planarity<N, E>(graph: &Graph<N, E>) -> Planarity {
+    let (vertices, edges) = indexed_edges(graph);
+    match LeftRight::new(vertices.len(), &edges).run() {
+        Some(rotation) => Planarity::Planar(PlanarEmbedding {
+            rotation: rotation
+                .into_iter()
+                .enumerate()
+                .map(|(idx, neighbours)| {
+                    let neighbours = neighbours.into_iter().map(|n| vertices[n]).collect();
+                    (vertices[idx], neighbours)
+                })
+                .collect(),
+        }),
+        None => {
+            let witness: Vec<(u32, u32)> = kuratowski_subgraph(vertices.len(), &edges)
+                .into_iter()
+                .map(|(first, second)| (vertices[first], vertices[second]))
+                .collect();
+            let mut witness = KuratowskiWitness {
+                edges: witness,
+                kind: KuratowskiKind::K33,
+            };
+            let branches = witness.branch_vertices();
+            if branches.len() == 5 {
+                witness.kind = KuratowskiKind::K5;
+            }
+            Planarity::NonPlanar(witness)
+        }
+    }
+}
+
+pub fn is_planar<N, E>(graph: &Graph<N, E>) -> bool {
+    let (vertices, edges) = indexed_edges(graph);
+    LeftRight::new(vertices.len(), &edges).run().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::{random_simple_graph, undirected};
+    use crate::graph::metrics::connected_components;
+    use crate::graph::Edge;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn complete(vertices: u32) -> Vec<(u32, u32)> {
+        let mut edges = Vec::new();
+        for first in 0..vertices {
+            for second in first + 1..vertices {
+                edges.push((first, second));
+            }
+        }
+        edges
+    }
+
+    fn assert_valid_embedding(graph: &Graph, embedding: &PlanarEmbedding) {
+        let mut edges = BTreeSet::new();
+        for vert in graph.vertices() {
+            let neighbours: BTreeSet<u32> = graph
+                .edges_from(vert)
+                .map(|edge| edge.to())
+                .filter(|to| *to != vert)
+                .collect();
+            let rotation = embedding.neighbours_cw(vert);
+            assert_eq!(rotation.len(), neighbours.len());
+            assert_eq!(
+                rotation.iter().copied().collect::<BTreeSet<u32>>(),
+                neighbours
+            );
+            for next in neighbours {
+                edges.insert((vert.min(next), vert.max(next)));
+            }
+        }
+        let components: Vec<Vec<u32>> = connected_components(graph)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .collect();
+        let vertices: usize = components.iter().map(|c| c.len()).sum();
+        let faces = embedding.faces().len();
+        assert_eq!(
+            vertices + faces,
+            edges.len() + 2 * components.len(),
+            "Euler's formula should hold for every component."
+        );
+    }
+
+    fn assert_minimal_witness(witness: &KuratowskiWitness) {
+        assert!(!is_planar(&undirected(witness.edges())));
+        for idx in 0..witness.edges().len() {
+            let mut rest = witness.edges().clone();
+            rest.remove(idx);
+            assert!(is_planar(&undirected(&rest)));
+        }
+        let expected = match witness.kind() {
+            KuratowskiKind::K5 => 5,
+            KuratowskiKind::K33 => 6,
+        };
+        assert_eq!(witness.branch_vertices().len(), expected);
+    }
+
+    #[test]
+    fn complete_graphs_up_to_four_vertices_are_planar() {
+        for vertices in 1..=4 {
+            let graph = undirected(&complete(vertices));
+            match planarity(&graph) {
+                Planarity::Planar(embedding) => assert_valid_embedding(&graph, &embedding),
+                Planarity::NonPlanar(_) => panic!("K{} should be planar.", vertices),
+            }
+        }
+    }
+
+    #[test]
+    fn k5_and_k33_are_their_own_witnesses() {
+        match planarity(&undirected(&complete(5))) {
+            Planarity::NonPlanar(witness) => {
+                assert_eq!(witness.kind(), KuratowskiKind::K5);
+                assert_eq!(witness.edges().len(), 10);
+            }
+            Planarity::Planar(_) => panic!("K5 should not be planar."),
+        }
+        let mut k33 = Vec::new();
+        for first in 1..=3 {
+            for second in 4..=6 {
+                k33.push((first, second));
+            }
+        }
+        match planarity(&undirected(&k33)) {
+            Planarity::NonPlanar(witness) => {
+                assert_eq!(witness.kind(), KuratowskiKind::K33);
+                assert_eq!(witness.edges().len(), 9);
+            }
+            Planarity::Planar(_) => panic!("K3,3 should not be planar."),
+        }
+    }
+
+    #[test]
+    fn directed_graphs_are_tested_through_their_underlying_graph() {
+        let mut graph: Graph = Graph::new();
+        for (first, second) in complete(5).iter() {
+            graph.add_adj(Edge::new(*second, *first));
+        }
+        graph.add_adj(Edge::new(0, 1));
+        graph.add_adj(Edge::new(2, 2));
+        assert!(!is_planar(&graph));
+        match planarity(&graph) {
+            Planarity::NonPlanar(witness) => assert_eq!(witness.edges().len(), 10),
+            Planarity::Planar(_) => panic!("A directed K5 should not be planar."),
+        }
+        let id = graph.edges_from(1).next().unwrap().id().unwrap();
+        graph.remove_edge(id);
+        assert!(!is_planar(&graph));
+        let id = graph.edges_from(0).next().unwrap().id().unwrap();
+        graph.remove_edge(id);
+        assert!(is_planar(&graph));
+    }
+
+    #[test]
+    fn petersen_graph_contains_a_k33_subdivision() {
+        let mut edges = Vec::new();
+        for i in 0..5 {
+            edges.push((i, (i + 1) % 5));
+            edges.push((i, i + 5));
+            edges.push((i + 5, (i + 2) % 5 + 5));
+        }
+        match planarity(&undirected(&edges)) {
+            Planarity::NonPlanar(witness) => {
+                assert_eq!(witness.kind(), KuratowskiKind::K33);
+                assert_minimal_witness(&witness);
+            }
+            Planarity::Planar(_) => panic!("Petersen graph should not be planar."),
+        }
+    }
+
+    #[test]
+    fn random_graphs_yield_embeddings_or_minimal_witnesses() {
+        let mut rng = StdRng::seed_from_u64(50);
+        let (mut planar, mut non_planar) = (0, 0);
+        for _ in 0..400 {
+            let vertices = rng.gen_range(2..12);
+            let probability = rng.gen_range(0.0..1.0);
+            let mut graph = random_simple_graph(&mut rng, vertices, probability);
+            graph.add_adj(Edge::new(0, 0));
+            match planarity(&graph) {
+                Planarity::Planar(embedding) => {
+                    planar += 1;
+                    assert_valid_embedding(&graph, &embedding);
+                }
+                Planarity::NonPlanar(witness) => {
+                    non_planar += 1;
+                    assert_minimal_witness(&witness);
+                }
+            }
+        }
+        assert!(planar > 50 && non_planar > 50);
+    }
+
+    #[test]
+    fn long_paths_do_not_overflow_the_stack() {
+        let mut edges: Vec<(u32, u32)> = (0..60_000).map(|v| (v, v + 1)).collect();
+        let graph = undirected(&edges);
+        match planarity(&graph) {
+            Planarity::Planar(embedding) => assert_valid_embedding(&graph, &embedding),
+            Planarity::NonPlanar(_) => panic!("A path should be planar."),
+        }
+        for (first, second) in complete(5).into_iter() {
+            edges.push((60_000 + first, 60_000 + second));
+        }
+        match planarity(&undirected(&edges)) {
+            Planarity::NonPlanar(witness) => {
+                assert_eq!(witness.kind(), KuratowskiKind::K5);
+                assert_eq!(witness.edges().len(), 10);
+            }
+            Planarity::Planar(_) => panic!("A path ending in K5 should not be planar."),
+        }
+    }
+}
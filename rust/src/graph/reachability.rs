@@ -0,0 +1,234 @@
+use crate::graph::{strongly_connected_components, Edge, Graph};
+use std::collections::HashMap;
+
+#[derive(Clone)]
+struct BitSet(Vec<u64>);
+
+impl BitSet {
+    fn new(size: usize) -> BitSet {
+        BitSet(vec![0; size.div_ceil(64)])
+    }
+
+    fn insert(&mut self, bit: usize) {
+        self.0[bit / 64] |= 1 << (bit % 64);
+    }
+
+    fn contains(&self, bit: usize) -> bool {
+        self.0[bit / 64] & (1 << (bit % 64)) != 0
+    }
+
+    fn union_with(&mut self, other: &BitSet) {
+        for (word, other_word) in self.0.iter_mut().zip(other.0.iter()) {
+            *word |= *other_word;
+        }
+    }
+}
+
+pub struct ReachabilityIndex<E = Option<u32>> {
+    component: HashMap<u32, usize>,
+    members: Vec<Vec<u32>>,
+    cyclic: Vec<bool>,
+    successors: Vec<Vec<(usize, Edge<E>)>>,
+    closure: Vec<BitSet>,
+}
+
+impl<E: Clone> ReachabilityIndex<E> {
+    pub fn new<N>(graph: &Graph<N, E>) -> ReachabilityIndex<E> {
+        let members = strongly_connected_components(graph);
+        let mut component = HashMap::new();
+        for (idx, scc) in members.iter().enumerate() {
+            for vert in scc.iter() {
+                component.insert(*vert, idx);
+            }
+        }
+        let mut cyclic: Vec<bool> = members.iter().map(|scc| scc.len() > 1).collect();
+        let mut successors: Vec<Vec<(usize, Edge<E>)>> = vec![Vec::new(); members.len()];
+        for (idx, scc) in members.iter().enumerate() {
+            for vert in scc.iter() {
+                for edge in graph.adj.get(vert).unwrap().iter() {
+                    let to = *component.get(&edge.to).unwrap();
+                    if to == idx {
+                        if edge.to == edge.from {
+                            cyclic[idx] = true;
+                        }
+                    } else if !successors[idx].iter().any(|(succ, _)| *succ == to) {
+                        successors[idx].push((to, edge.clone()));
+                    }
+                }
+            }
+        }
+        let mut closure = vec![BitSet::new(members.len()); members.len()];
+        for idx in (0..members.len()).rev() {
+            let mut reachable = BitSet::new(members.len());
+            reachable.insert(idx);
+            for (succ, _) in successors[idx].iter() {
+                reachable.union_with(&closure[*succ]);
+            }
+            closure[idx] = reachable;
+        }
+        ReachabilityIndex {
+            component,
+            members,
+            cyclic,
+            successors,
+            closure,
+        }
+    }
+
+    /// Reachability means a path of at least one edge, so a vertex reaches
+    /// itself only through a cycle or a self-loop. `transitive_closure`
+    /// contains exactly the pairs for which this returns `true`.
+    pub fn reaches(&self, from: u32, to: u32) -> bool {
+        match (self.component.get(&from), self.component.get(&to)) {
+            (Some(from_idx), Some(to_idx)) if from_idx == to_idx => self.cyclic[*from_idx],
+            (Some(from_idx), Some(to_idx)) => self.closure[*from_idx].contains(*to_idx),
+            _ => false,
+        }
+    }
+
+    pub fn same_component(&self, first: u32, second: u32) -> bool {
+        match (self.component.get(&first), self.component.get(&second)) {
+            (Some(first_idx), Some(second_idx)) => first_idx == second_idx,
+            _ => false,
+        }
+    }
+
+    pub fn components(&self) -> &Vec<Vec<u32>> {
+        &self.members
+    }
+
+    pub fn transitive_closure(&self) -> Graph {
+        let mut result = Graph::new();
+        for (from_idx, from_scc) in self.members.iter().enumerate() {
+            for from in from_scc.iter() {
+                result.add_vertex(*from);
+                for (to_idx, to_scc) in self.members.iter().enumerate() {
+                    if !self.closure[from_idx].contains(to_idx) {
+                        continue;
+                    }
+                    for to in to_scc.iter() {
+                        if from != to || self.cyclic[from_idx] {
+                            result.add_adj(Edge::new(*from, *to));
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    pub fn transitive_reduction(&self) -> Graph<(), E>
+    where
+        E: Default,
+    {
+        let mut result = Graph::new();
+        for (idx, scc) in self.members.iter().enumerate() {
+            for vert in scc.iter() {
+                result.add_vertex(*vert);
+            }
+            if self.cyclic[idx] {
+                for pos in 0..scc.len() {
+                    let (from, to) = (scc[pos], scc[(pos + 1) % scc.len()]);
+                    result.add_adj(Edge::with_value(E::default(), from, to));
+                }
+            }
+            for (succ, edge) in self.successors[idx].iter() {
+                let redundant = self.successors[idx]
+                    .iter()
+                    .any(|(other, _)| other != succ && self.closure[*other].contains(*succ));
+                if !redundant {
+                    result.add_adj(edge.clone());
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::{directed, random_multigraph};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::BTreeSet;
+
+    fn reachable<N, E>(graph: &Graph<N, E>, start: u32) -> BTreeSet<u32> {
+        let mut seen = BTreeSet::new();
+        let mut stack: Vec<u32> = graph.edges_from(start).map(|edge| edge.to()).collect();
+        while let Some(vert) = stack.pop() {
+            if seen.insert(vert) {
+                stack.extend(graph.edges_from(vert).map(|edge| edge.to()));
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn queries_match_graph_search() {
+        let mut rng = StdRng::seed_from_u64(28);
+        for _ in 0..50 {
+            let graph = random_multigraph(&mut rng, 12, 16, 1..2, false);
+            let index = ReachabilityIndex::new(&graph);
+            for from in 0..12 {
+                let expected = reachable(&graph, from);
+                for to in 0..12 {
+                    assert_eq!(index.reaches(from, to), expected.contains(&to));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn closure_and_reduction_preserve_reachability() {
+        let mut rng = StdRng::seed_from_u64(280);
+        for _ in 0..50 {
+            let graph = random_multigraph(&mut rng, 10, 14, 1..2, false);
+            let index = ReachabilityIndex::new(&graph);
+            let closure = index.transitive_closure();
+            let reduction = index.transitive_reduction();
+            for vert in 0..10 {
+                let expected = reachable(&graph, vert);
+                let direct: BTreeSet<u32> = closure.edges_from(vert).map(|e| e.to()).collect();
+                assert_eq!(direct, expected);
+                for to in 0..10 {
+                    assert_eq!(index.reaches(vert, to), direct.contains(&to));
+                }
+                assert_eq!(reachable(&reduction, vert), expected);
+            }
+            assert!(reduction.edge_count() <= graph.edge_count());
+        }
+    }
+
+    #[test]
+    fn reduction_of_a_dag_drops_shortcuts() {
+        let graph = directed(&[(1, 2), (2, 3), (1, 3), (3, 4), (1, 4), (2, 4)]);
+        let reduction = ReachabilityIndex::new(&graph).transitive_reduction();
+        assert_eq!(reduction.edge_count(), 3);
+        assert!(reduction.edge(1, 2).is_some());
+        assert!(reduction.edge(2, 3).is_some());
+        assert!(reduction.edge(3, 4).is_some());
+    }
+
+    #[test]
+    fn components_and_self_loops() {
+        let graph = directed(&[(1, 2), (2, 1), (2, 3), (4, 4)]);
+        let index = ReachabilityIndex::new(&graph);
+        assert!(index.same_component(1, 2));
+        assert!(!index.same_component(2, 3));
+        assert!(!index.same_component(1, 9));
+        assert!(!index.reaches(3, 1));
+        assert!(!index.reaches(9, 9));
+        assert!(index.reaches(1, 1));
+        assert!(index.reaches(4, 4));
+        assert!(!index.reaches(3, 3));
+        assert_eq!(index.components().len(), 3);
+        let closure = index.transitive_closure();
+        assert!(closure.edge(1, 1).is_some());
+        assert!(closure.edge(4, 4).is_some());
+        assert!(closure.edge(3, 3).is_none());
+        let reduction = index.transitive_reduction();
+        assert!(reduction.edge(4, 4).is_some());
+        assert!(reduction.edge(3, 3).is_none());
+    }
+}
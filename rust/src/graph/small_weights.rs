@@ -0,0 +1,276 @@
+use crate::graph::{recreate_path, shortest_path_dijkstra_by, Edge, Graph, Path};
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+const DIAL_MAX_BUCKETS: usize = 1 << 16;
+
+pub struct BfsLayers {
+    distances: BTreeMap<u32, usize>,
+    layers: Vec<Vec<u32>>,
+}
+
+impl BfsLayers {
+    pub fn distance(&self, vert: u32) -> Option<usize> {
+        self.distances.get(&vert).copied()
+    }
+
+    pub fn distances(&self) -> &BTreeMap<u32, usize> {
+        &self.distances
+    }
+
+    pub fn layer(&self, hops: usize) -> &[u32] {
+        match self.layers.get(hops) {
+            None => &[],
+            Some(layer) => layer.as_slice(),
+        }
+    }
+
+    pub fn layers(&self) -> &Vec<Vec<u32>> {
+        &self.layers
+    }
+
+    pub fn is_reachable(&self, vert: u32) -> bool {
+        self.distances.contains_key(&vert)
+    }
+}
+
+pub fn bfs_distances<N, E>(graph: &Graph<N, E>, start: u32) -> BfsLayers {
+    let mut result = BfsLayers {
+        distances: BTreeMap::new(),
+        layers: Vec::new(),
+    };
+    if !graph.contains_vertex(start) {
+        return result;
+    }
+    result.distances.insert(start, 0);
+    let mut frontier = vec![start];
+    while !frontier.is_empty() {
+        let hops = result.layers.len() + 1;
+        let mut next = Vec::new();
+        for vert in frontier.iter() {
+            for edge in graph.edges_from(*vert) {
+                if let Entry::Vacant(entry) = result.distances.entry(edge.to) {
+                    entry.insert(hops);
+                    next.push(edge.to);
+                }
+            }
+        }
+        frontier.sort_unstable();
+        result.layers.push(frontier);
+        frontier = next;
+    }
+    result
+}
+
+type ZeroOneResult<E> = Result<Option<Path<u32, Edge<E>, u32>>, InvalidWeight<E>>;
+
+pub struct InvalidWeight<E = Option<u32>> {
+    edge: Edge<E>,
+    weight: u32,
+}
+
+impl<E> InvalidWeight<E> {
+    pub fn edge(&self) -> &Edge<E> {
+        &self.edge
+    }
+
+    pub fn weight(&self) -> u32 {
+        self.weight
+    }
+}
+
+pub fn shortest_path_zero_one_bfs(
+    graph: &Graph,
+    start: u32,
+    end: u32,
+) -> Result<Option<Path>, InvalidWeight> {
+    shortest_path_zero_one_bfs_by(graph, start, end, |value| value.unwrap_or(1))
+}
+
+pub fn shortest_path_zero_one_bfs_by<N, E: Clone>(
+    graph: &Graph<N, E>,
+    start: u32,
+    end: u32,
+    weight: impl Fn(&E) -> u32,
+) -> ZeroOneResult<E> {
+    let invalid = graph
+        .adj
+        .values()
+        .flat_map(|edges| edges.iter())
+        .find(|edge| weight(&edge.value) > 1);
+    if let Some(edge) = invalid {
+        return Result::Err(InvalidWeight {
+            edge: edge.clone(),
+            weight: weight(&edge.value),
+        });
+    }
+    if !graph.contains_vertex(start) {
+        return Result::Ok(Option::None);
+    }
+    let mut distance: HashMap<u32, u32> = HashMap::new();
+    let mut predecessors: HashMap<u32, u32> = HashMap::new();
+    let mut deque = VecDeque::new();
+    distance.insert(start, 0);
+    deque.push_back((start, 0));
+    while let Some((vert, dist)) = deque.pop_front() {
+        if dist > *distance.get(&vert).unwrap() {
+            continue;
+        }
+        for edge in graph.edges_from(vert) {
+            let step = weight(&edge.value);
+            let dist_through = dist + step;
+            if distance.get(&edge.to).is_none_or(|d| dist_through < *d) {
+                distance.insert(edge.to, dist_through);
+                predecessors.insert(edge.to, vert);
+                if step == 0 {
+                    deque.push_front((edge.to, dist_through));
+                } else {
+                    deque.push_back((edge.to, dist_through));
+                }
+            }
+        }
+    }
+    Result::Ok(recreate_path(graph, start, end, &predecessors, weight))
+}
+
+pub fn shortest_path_dial(graph: &Graph, start: u32, end: u32) -> Option<Path> {
+    shortest_path_dial_by(graph, start, end, |value| value.unwrap_or(1))
+}
+
+pub fn shortest_path_dial_by<N, E: Clone>(
+    graph: &Graph<N, E>,
+    start: u32,
+    end: u32,
+    weight: impl Fn(&E) -> u32,
+) -> Option<Path<u32, Edge<E>, u32>> {
+    if !graph.contains_vertex(start) {
+        return Option::None;
+    }
+    let max_weight = graph
+        .adj
+        .values()
+        .flat_map(|edges| edges.iter())
+        .map(|edge| weight(&edge.value))
+        .max()
+        .unwrap_or(0) as usize;
+    if max_weight >= DIAL_MAX_BUCKETS {
+        return shortest_path_dijkstra_by(graph, start, end, weight);
+    }
+    let mut buckets: Vec<Vec<u32>> = vec![Vec::new(); max_weight + 1];
+    let mut distance: HashMap<u32, u32> = HashMap::new();
+    let mut predecessors: HashMap<u32, u32> = HashMap::new();
+    distance.insert(start, 0);
+    buckets[0].push(start);
+    let mut pending = 1;
+    let mut curr = 0u32;
+    while pending > 0 {
+        let bucket = curr as usize % buckets.len();
+        while let Some(vert) = buckets[bucket].pop() {
+            pending -= 1;
+            if *distance.get(&vert).unwrap() != curr {
+                continue;
+            }
+            for edge in graph.edges_from(vert) {
+                let dist_through = curr + weight(&edge.value);
+                if distance.get(&edge.to).is_none_or(|d| dist_through < *d) {
+                    distance.insert(edge.to, dist_through);
+                    predecessors.insert(edge.to, vert);
+                    let len = buckets.len();
+                    buckets[dist_through as usize % len].push(edge.to);
+                    pending += 1;
+                }
+            }
+        }
+        curr += 1;
+    }
+    recreate_path(graph, start, end, &predecessors, weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::directed_weighted;
+    use crate::graph::shortest_path_dijkstra;
+
+    #[test]
+    fn bfs_layers_group_vertices_by_hops() {
+        let graph = directed_weighted(&[
+            (1, 2, 9),
+            (1, 3, 9),
+            (2, 4, 9),
+            (3, 4, 9),
+            (4, 5, 9),
+            (6, 1, 9),
+        ]);
+        let layers = bfs_distances(&graph, 1);
+        assert_eq!(layers.layer(0), &[1]);
+        assert_eq!(layers.layer(1), &[2, 3]);
+        assert_eq!(layers.layer(2), &[4]);
+        assert_eq!(layers.layer(3), &[5]);
+        assert!(layers.layer(4).is_empty());
+        assert_eq!(layers.distance(5), Option::Some(3));
+        assert!(!layers.is_reachable(6));
+        assert_eq!(
+            layers.distances().keys().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert!(bfs_distances(&graph, 42).layers().is_empty());
+    }
+
+    #[test]
+    fn zero_one_bfs_prefers_free_edges() {
+        let graph = directed_weighted(&[(1, 2, 1), (1, 3, 0), (3, 4, 0), (4, 2, 0), (2, 5, 1)]);
+        let path = shortest_path_zero_one_bfs(&graph, 1, 5)
+            .ok()
+            .unwrap()
+            .unwrap();
+        assert_eq!(path.vertices(), &vec![1, 3, 4, 2, 5]);
+        assert_eq!(path.cost(), 1);
+        let unreachable = shortest_path_zero_one_bfs(&graph, 5, 1).ok().unwrap();
+        assert!(unreachable.is_none());
+        assert!(shortest_path_zero_one_bfs(&graph, 9, 1)
+            .ok()
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn zero_one_bfs_rejects_heavier_edges() {
+        let graph = directed_weighted(&[(1, 2, 1), (2, 3, 2)]);
+        match shortest_path_zero_one_bfs(&graph, 1, 2) {
+            Result::Ok(_) => panic!("a weight of 2 is not a 0-1 weight"),
+            Result::Err(invalid) => {
+                assert_eq!(invalid.weight(), 2);
+                assert_eq!((invalid.edge().from(), invalid.edge().to()), (2, 3));
+            }
+        }
+    }
+
+    #[test]
+    fn dial_matches_dijkstra() {
+        let graph = directed_weighted(&[
+            (1, 2, 4),
+            (1, 3, 1),
+            (3, 2, 2),
+            (2, 4, 1),
+            (3, 4, 5),
+            (4, 5, 3),
+            (2, 5, 7),
+        ]);
+        for end in 2..=5 {
+            let dial = shortest_path_dial(&graph, 1, end).unwrap();
+            let dijkstra = shortest_path_dijkstra(&graph, 1, end).unwrap();
+            assert_eq!(dial.cost(), dijkstra.cost());
+            assert_eq!(dial.vertices(), dijkstra.vertices());
+        }
+        assert!(shortest_path_dial(&graph, 5, 1).is_none());
+    }
+
+    #[test]
+    fn dial_falls_back_to_dijkstra_for_large_weights() {
+        let graph = directed_weighted(&[(1, 2, u32::MAX / 4), (2, 3, 7), (1, 3, u32::MAX / 2)]);
+        let path = shortest_path_dial(&graph, 1, 3).unwrap();
+        assert_eq!(path.vertices(), &vec![1, 2, 3]);
+        assert_eq!(path.cost(), u32::MAX / 4 + 7);
+    }
+}
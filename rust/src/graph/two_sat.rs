@@ -0,0 +1,175 @@
+use crate::graph::{strongly_connected_components, Edge, Graph};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Literal {
+    var: u32,
+    positive: bool,
+}
+
+impl Literal {
+    pub fn positive(var: u32) -> Literal {
+        Literal {
+            var,
+            positive: true,
+        }
+    }
+
+    pub fn negative(var: u32) -> Literal {
+        Literal {
+            var,
+            positive: false,
+        }
+    }
+
+    pub fn var(&self) -> u32 {
+        self.var
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.positive
+    }
+
+    pub fn negate(&self) -> Literal {
+        Literal {
+            var: self.var,
+            positive: !self.positive,
+        }
+    }
+}
+
+pub struct TwoSat {
+    variables: BTreeMap<u32, u32>,
+    graph: Graph,
+}
+
+impl TwoSat {
+    pub fn new() -> TwoSat {
+        TwoSat {
+            variables: BTreeMap::new(),
+            graph: Graph::new(),
+        }
+    }
+
+    pub fn add_variable(&mut self, var: u32) {
+        if self.variables.contains_key(&var) {
+            return;
+        }
+        let index = self.variables.len() as u32;
+        self.variables.insert(var, index);
+        self.graph.add_vertex(self.vertex(Literal::positive(var)));
+        self.graph.add_vertex(self.vertex(Literal::negative(var)));
+    }
+
+    fn vertex(&self, literal: Literal) -> u32 {
+        let index = self.variables[&literal.var];
+        if literal.positive {
+            2 * index
+        } else {
+            2 * index + 1
+        }
+    }
+
+    pub fn add_clause(&mut self, first: Literal, second: Literal) {
+        self.add_variable(first.var);
+        self.add_variable(second.var);
+        let implication = Edge::new(self.vertex(first.negate()), self.vertex(second));
+        self.graph.add_adj(implication);
+        let implication = Edge::new(self.vertex(second.negate()), self.vertex(first));
+        self.graph.add_adj(implication);
+    }
+
+    pub fn add_unit(&mut self, literal: Literal) {
+        self.add_clause(literal, literal);
+    }
+
+    pub fn add_implication(&mut self, premise: Literal, conclusion: Literal) {
+        self.add_clause(premise.negate(), conclusion);
+    }
+
+    pub fn implication_graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    pub fn solve(&self) -> Result<BTreeMap<u32, bool>, u32> {
+        let mut component: HashMap<u32, usize> = HashMap::new();
+        for (idx, members) in strongly_connected_components(&self.graph)
+            .iter()
+            .enumerate()
+        {
+            for vert in members.iter() {
+                component.insert(*vert, idx);
+            }
+        }
+        let mut result = BTreeMap::new();
+        for var in self.variables.keys() {
+            let positive = component[&self.vertex(Literal::positive(*var))];
+            let negative = component[&self.vertex(Literal::negative(*var))];
+            if positive == negative {
+                return Result::Err(*var);
+            }
+            result.insert(*var, positive > negative);
+        }
+        Result::Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn satisfies(clauses: &[(Literal, Literal)], assignment: &BTreeMap<u32, bool>) -> bool {
+        let holds = |literal: &Literal| assignment[&literal.var()] == literal.is_positive();
+        clauses
+            .iter()
+            .all(|(first, second)| holds(first) || holds(second))
+    }
+
+    #[test]
+    fn satisfiable_formula_yields_a_model() {
+        let clauses = [
+            (Literal::positive(1), Literal::positive(2)),
+            (Literal::negative(1), Literal::positive(3)),
+            (Literal::negative(2), Literal::negative(3)),
+            (Literal::positive(1), Literal::negative(3)),
+        ];
+        let mut formula = TwoSat::new();
+        for (first, second) in clauses.iter() {
+            formula.add_clause(*first, *second);
+        }
+        formula.add_variable(9);
+        let assignment = match formula.solve() {
+            Ok(assignment) => assignment,
+            Err(var) => panic!("Formula should be satisfiable, failed on {}.", var),
+        };
+        assert_eq!(
+            assignment.keys().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 9]
+        );
+        assert!(satisfies(&clauses, &assignment));
+    }
+
+    #[test]
+    fn contradictory_units_are_unsatisfiable() {
+        let mut formula = TwoSat::new();
+        formula.add_implication(Literal::positive(1), Literal::positive(2));
+        formula.add_implication(Literal::positive(2), Literal::negative(1));
+        formula.add_unit(Literal::positive(1));
+        assert!(matches!(formula.solve(), Err(1) | Err(2)));
+    }
+
+    #[test]
+    fn large_variable_ids_do_not_overflow() {
+        let high = u32::MAX;
+        let mut formula = TwoSat::new();
+        formula.add_unit(Literal::negative(high));
+        formula.add_clause(Literal::positive(high), Literal::positive(1 << 31));
+        assert_eq!(formula.implication_graph().vertex_count(), 4);
+        let assignment = match formula.solve() {
+            Ok(assignment) => assignment,
+            Err(_) => panic!("Formula should be satisfiable."),
+        };
+        assert!(!assignment[&high]);
+        assert!(assignment[&(1 << 31)]);
+    }
+}
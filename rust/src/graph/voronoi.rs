@@ -0,0 +1,160 @@
+use crate::graph::Graph;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+
+pub struct Voronoi {
+    nearest: BTreeMap<u32, (u32, u64)>,
+    predecessors: BTreeMap<u32, u32>,
+}
+
+impl Voronoi {
+    pub fn nearest_source(&self, vert: u32) -> Option<u32> {
+        self.nearest.get(&vert).map(|(source, _)| *source)
+    }
+
+    pub fn distance(&self, vert: u32) -> Option<u64> {
+        self.nearest.get(&vert).map(|(_, dist)| *dist)
+    }
+
+    pub fn predecessor(&self, vert: u32) -> Option<u32> {
+        self.predecessors.get(&vert).copied()
+    }
+
+    pub fn cell(&self, source: u32) -> Vec<u32> {
+        self.nearest
+            .iter()
+            .filter(|(_, (nearest, _))| *nearest == source)
+            .map(|(vert, _)| *vert)
+            .collect()
+    }
+
+    pub fn cells(&self) -> BTreeMap<u32, Vec<u32>> {
+        let mut result: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        for (vert, (source, _)) in self.nearest.iter() {
+            result.entry(*source).or_default().push(*vert);
+        }
+        result
+    }
+}
+
+pub fn multi_source_dijkstra(graph: &Graph, sources: &[u32]) -> Voronoi {
+    multi_source_dijkstra_by(graph, sources, |value| value.unwrap_or(1) as u64)
+}
+
+pub fn multi_source_dijkstra_by<N, E>(
+    graph: &Graph<N, E>,
+    sources: &[u32],
+    weight: impl Fn(&E) -> u64,
+) -> Voronoi {
+    let sources: Vec<(u32, u64)> = sources.iter().map(|source| (*source, 0)).collect();
+    multi_source_dijkstra_with_offsets_by(graph, &sources, weight)
+}
+
+pub fn multi_source_dijkstra_with_offsets(graph: &Graph, sources: &[(u32, u64)]) -> Voronoi {
+    multi_source_dijkstra_with_offsets_by(graph, sources, |value| value.unwrap_or(1) as u64)
+}
+
+pub fn multi_source_dijkstra_with_offsets_by<N, E>(
+    graph: &Graph<N, E>,
+    sources: &[(u32, u64)],
+    weight: impl Fn(&E) -> u64,
+) -> Voronoi {
+    let mut result = Voronoi {
+        nearest: BTreeMap::new(),
+        predecessors: BTreeMap::new(),
+    };
+    let mut heap = BinaryHeap::new();
+    for (source, offset) in sources.iter() {
+        if graph.contains_vertex(*source) {
+            heap.push(Reverse((*offset, *source, *source, Option::None)));
+        }
+    }
+    while let Some(Reverse((dist, source, vert, predecessor))) = heap.pop() {
+        if result.nearest.contains_key(&vert) {
+            continue;
+        }
+        result.nearest.insert(vert, (source, dist));
+        if let Some(predecessor) = predecessor {
+            result.predecessors.insert(vert, predecessor);
+        }
+        for edge in graph.edges_from(vert) {
+            if !result.nearest.contains_key(&edge.to) {
+                let dist_through = dist + weight(&edge.value);
+                heap.push(Reverse((dist_through, source, edge.to, Option::Some(vert))));
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::fixtures::{random_multigraph, undirected};
+    use crate::graph::shortest_path_dijkstra;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn path_graph() -> Graph {
+        let mut graph = undirected(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+        graph.add_vertex(9);
+        graph
+    }
+
+    #[test]
+    fn ties_go_to_the_smaller_source() {
+        let graph = path_graph();
+        let voronoi = multi_source_dijkstra(&graph, &[5, 1, 7]);
+        assert_eq!(voronoi.nearest_source(3), Option::Some(1));
+        assert_eq!(voronoi.distance(3), Option::Some(2));
+        assert_eq!(voronoi.cell(1), vec![1, 2, 3]);
+        assert_eq!(voronoi.cell(5), vec![4, 5]);
+        assert_eq!(
+            voronoi.cells().keys().copied().collect::<Vec<_>>(),
+            vec![1, 5]
+        );
+        assert_eq!(voronoi.predecessor(3), Option::Some(2));
+        assert_eq!(voronoi.predecessor(1), Option::None);
+        assert_eq!(voronoi.nearest_source(9), Option::None);
+        assert_eq!(voronoi.distance(9), Option::None);
+    }
+
+    #[test]
+    fn offsets_and_weights_shift_the_boundary() {
+        let graph = path_graph();
+        let voronoi = multi_source_dijkstra_with_offsets(&graph, &[(1, 3), (5, 0)]);
+        assert_eq!(voronoi.cell(1), vec![1]);
+        assert_eq!(voronoi.cell(5), vec![2, 3, 4, 5]);
+        assert_eq!(voronoi.distance(1), Option::Some(3));
+        assert_eq!(voronoi.distance(2), Option::Some(3));
+        let voronoi = multi_source_dijkstra_by(&graph, &[1, 5], |_| 0);
+        assert_eq!(voronoi.cell(1), vec![1, 2, 3, 4, 5]);
+        assert_eq!(voronoi.distance(4), Option::Some(0));
+    }
+
+    #[test]
+    fn distances_match_the_closest_single_source_search() {
+        let mut rng = StdRng::seed_from_u64(46);
+        for _ in 0..50 {
+            let vertices = rng.gen_range(2..15);
+            let edges = rng.gen_range(0..vertices * 3) as usize;
+            let graph = random_multigraph(&mut rng, vertices, edges, 0..10, false);
+            let sources: Vec<u32> = (0..vertices).filter(|_| rng.gen_bool(0.3)).collect();
+            let voronoi = multi_source_dijkstra(&graph, &sources);
+            for vert in 0..vertices {
+                let expected = sources
+                    .iter()
+                    .filter_map(|source| {
+                        shortest_path_dijkstra(&graph, *source, vert)
+                            .map(|path| (path.cost() as u64, *source))
+                    })
+                    .min();
+                assert_eq!(voronoi.distance(vert), expected.map(|(dist, _)| dist));
+                assert_eq!(
+                    voronoi.nearest_source(vert),
+                    expected.map(|(_, source)| source)
+                );
+            }
+        }
+    }
+}